@@ -0,0 +1,235 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::format_ident;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::parse_quote;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Expr;
+use syn::Fields;
+use syn::GenericParam;
+use syn::LitInt;
+use syn::LitStr;
+use syn::Result;
+
+#[derive(Clone, Copy)]
+struct Layout {
+    big: bool,
+    prefix: usize,
+    tag: usize,
+    id: Option<u64>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self { big: false, prefix: 2, tag: 1, id: None }
+    }
+}
+
+impl Layout {
+    fn parse(mut self, attrs: &[Attribute]) -> Result<Self> {
+        self.id = None;
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("packet")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("big") {
+                    self.big = true;
+                } else if meta.path.is_ident("little") {
+                    self.big = false;
+                } else if meta.path.is_ident("endian") {
+                    let endian: LitStr = meta.value()?.parse()?;
+                    self.big = match endian.value().as_str() {
+                        "big" => true,
+                        "little" => false,
+                        _ => return Err(meta.error("expected \"big\" or \"little\"")),
+                    };
+                } else if meta.path.is_ident("prefix") {
+                    self.prefix = width(meta.value()?.parse()?)?;
+                } else if meta.path.is_ident("tag") {
+                    self.tag = width(meta.value()?.parse()?)?;
+                } else if meta.path.is_ident("id") {
+                    let id: LitInt = meta.value()?.parse()?;
+                    self.id = Some(id.base10_parse()?);
+                } else {
+                    return Err(meta.error("unsupported packet attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(self)
+    }
+
+    fn tokens(&self) -> TokenStream2 {
+        let endian = if self.big { quote!(Big) } else { quote!(Little) };
+        let prefix = self.prefix;
+        quote!(::tcp::Layout::new(::tcp::Endian::#endian, #prefix))
+    }
+
+    fn tag_type(&self) -> TokenStream2 {
+        match self.tag {
+            1 => quote!(u8),
+            2 => quote!(u16),
+            4 => quote!(u32),
+            _ => quote!(u64),
+        }
+    }
+}
+
+fn width(lit: LitInt) -> Result<usize> {
+    match lit.base10_parse()? {
+        n @ (1 | 2 | 4 | 8) => Ok(n),
+        _ => Err(Error::new(lit.span(), "width must be 1, 2, 4 or 8")),
+    }
+}
+
+fn encode_fields(fields: &Fields, layout: Layout, bindings: &[TokenStream2]) -> Result<TokenStream2> {
+    let mut tokens = TokenStream2::new();
+    for (field, binding) in fields.iter().zip(bindings) {
+        let layout = layout.parse(&field.attrs)?.tokens();
+        tokens.extend(quote! {
+            ::tcp::PacketField::encode_field(#binding, __buf, #layout)?;
+        });
+    }
+    Ok(tokens)
+}
+
+fn decode_fields(fields: &Fields, layout: Layout) -> Result<TokenStream2> {
+    let mut values = Vec::new();
+    for field in fields.iter() {
+        let layout = layout.parse(&field.attrs)?.tokens();
+        let value = quote!(::tcp::PacketField::decode_field(__buf, #layout)?);
+        values.push(match &field.ident {
+            Some(ident) => quote!(#ident: #value),
+            None => value,
+        });
+    }
+
+    Ok(match fields {
+        Fields::Named(_) => quote!({ #(#values),* }),
+        Fields::Unnamed(_) => quote!(( #(#values),* )),
+        Fields::Unit => quote!(),
+    })
+}
+
+fn bindings(fields: &Fields) -> (TokenStream2, Vec<TokenStream2>) {
+    let idents: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| field.ident.clone().unwrap_or_else(|| format_ident!("field{}", i)))
+        .collect();
+    let pattern = match fields {
+        Fields::Named(_) => quote!({ #(#idents),* }),
+        Fields::Unnamed(_) => quote!(( #(#idents),* )),
+        Fields::Unit => quote!(),
+    };
+    (pattern, idents.iter().map(|ident| quote!(#ident)).collect())
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2> {
+    let layout = Layout::default().parse(&input.attrs)?;
+    let name = &input.ident;
+
+    let (encode, decode) = match &input.data {
+        Data::Struct(data) => {
+            let bindings: Vec<_> = data.fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| match &field.ident {
+                    Some(ident) => quote!(&self.#ident),
+                    None => {
+                        let index = syn::Index::from(i);
+                        quote!(&self.#index)
+                    },
+                })
+                .collect();
+            let encode = encode_fields(&data.fields, layout, &bindings)?;
+            let decode = decode_fields(&data.fields, layout)?;
+            (encode, quote!(Ok(Self #decode)))
+        },
+        Data::Enum(data) => {
+            let tag = layout.tag_type();
+            let tag_layout = layout.tokens();
+            let mut next = 0u64;
+            let mut encodes = Vec::new();
+            let mut decodes = Vec::new();
+
+            for variant in data.variants.iter() {
+                let attrs = layout.parse(&variant.attrs)?;
+                let id = match (attrs.id, &variant.discriminant) {
+                    (Some(id), _) => id,
+                    (None, Some((_, Expr::Lit(lit)))) => match &lit.lit {
+                        syn::Lit::Int(int) => int.base10_parse()?,
+                        _ => return Err(Error::new_spanned(lit, "unsupported discriminant")),
+                    },
+                    (None, Some((_, expr))) => {
+                        return Err(Error::new_spanned(expr, "unsupported discriminant"))
+                    },
+                    (None, None) => next,
+                };
+                next = id + 1;
+
+                let ident = &variant.ident;
+                let id = LitInt::new(&format!("{}{}", id, tag), Span::call_site());
+                let (pattern, bindings) = bindings(&variant.fields);
+                let fields = encode_fields(&variant.fields, attrs, &bindings)?;
+                let values = decode_fields(&variant.fields, attrs)?;
+
+                encodes.push(quote! {
+                    Self::#ident #pattern => {
+                        ::tcp::PacketField::encode_field(&#id, __buf, #tag_layout)?;
+                        #fields
+                    }
+                });
+                decodes.push(quote!(#id => Ok(Self::#ident #values)));
+            }
+
+            let encode = quote! {
+                match self {
+                    #(#encodes),*
+                }
+            };
+            let decode = quote! {
+                let __tag: #tag = ::tcp::PacketField::decode_field(__buf, #tag_layout)?;
+                match __tag {
+                    #(#decodes,)*
+                    __tag => Err(::tcp::Error::Codec(format!("unknown packet tag {}", __tag))),
+                }
+            };
+            (encode, decode)
+        },
+        Data::Union(_) => return Err(Error::new_spanned(&input, "unions are not supported")),
+    };
+
+    for param in input.generics.params.iter_mut() {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::tcp::PacketField));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::tcp::Packet for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn encode(&self, __buf: &mut ::tcp::__private::BytesMut) -> Result<(), ::tcp::Error> {
+                #encode
+                Ok(())
+            }
+
+            #[allow(unused_variables)]
+            fn decode(__buf: &mut ::tcp::__private::Bytes) -> Result<Self, ::tcp::Error> {
+                #decode
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(Packet, attributes(packet))]
+pub fn derive_packet(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
\ No newline at end of file
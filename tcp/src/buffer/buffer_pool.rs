@@ -0,0 +1,151 @@
+use bytes::Bytes;
+use bytes::BytesMut;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+const OVERSIZE_FACTOR: usize = 4;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoolStats {
+    pub pooled: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub discards: u64,
+}
+
+#[derive(Debug)]
+pub struct BufferPool {
+    size: usize,
+    capacity: usize,
+    buffers: Mutex<Vec<BytesMut>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    discards: AtomicU64,
+}
+
+impl PoolStats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl BufferPool {
+    pub fn new(size: usize, capacity: usize) -> Self {
+        Self {
+            size,
+            capacity,
+            buffers: Mutex::new(Vec::with_capacity(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            discards: AtomicU64::new(0),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn acquire(&self) -> BytesMut {
+        match self.buffers.lock().unwrap().pop() {
+            Some(buf) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                buf
+            },
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                BytesMut::with_capacity(self.size)
+            },
+        }
+    }
+
+    /// Keeps `buf` only if its memory can be reused without allocating, so every
+    /// pooled buffer, and every hit, is memory that actually came back.
+    pub fn release(&self, mut buf: BytesMut) {
+        buf.clear();
+        if !buf.try_reclaim(self.size) || buf.capacity() > self.size.saturating_mul(OVERSIZE_FACTOR) {
+            self.discards.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        let mut buffers = self.buffers.lock().unwrap();
+        if buffers.len() < self.capacity {
+            buffers.push(buf);
+        } else {
+            self.discards.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn reclaim(&self, bytes: Bytes) {
+        if let Ok(buf) = bytes.try_into_mut() {
+            if buf.capacity() >= self.size {
+                self.release(buf);
+            }
+        }
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            pooled: self.buffers.lock().unwrap().len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            discards: self.discards.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BufMut;
+
+    #[test]
+    fn reclaims_unique_buffers() {
+        let pool = BufferPool::new(64, 4);
+        let mut buf = pool.acquire();
+        buf.put_slice(b"frame");
+        pool.reclaim(buf.freeze());
+
+        let stats = pool.stats();
+        assert_eq!((stats.pooled, stats.misses, stats.discards), (1, 1, 0));
+        assert!(pool.acquire().capacity() >= 64);
+        assert_eq!(pool.stats().hits, 1);
+    }
+
+    #[test]
+    fn skips_shared_and_foreign_buffers() {
+        let pool = BufferPool::new(64, 4);
+        let mut buf = pool.acquire();
+        buf.put_slice(b"frame");
+        let frame = buf.freeze();
+        let shared = frame.clone();
+        pool.reclaim(frame);
+        pool.reclaim(Bytes::from_static(b"static"));
+        pool.reclaim(Bytes::from(vec![0; 8]));
+        assert_eq!(pool.stats().pooled, 0);
+
+        drop(shared);
+        assert_eq!(pool.stats().discards, 0);
+    }
+
+    #[test]
+    fn discards_buffers_that_cannot_be_reused() {
+        let pool = BufferPool::new(64, 1);
+        pool.release(BytesMut::with_capacity(64 * OVERSIZE_FACTOR + 1));
+
+        let mut buf = BytesMut::with_capacity(128);
+        buf.put_slice(&[0; 100]);
+        let frame = buf.split_to(100);
+        pool.release(buf);
+
+        pool.release(BytesMut::with_capacity(64));
+        pool.release(BytesMut::with_capacity(64));
+        drop(frame);
+
+        let stats = pool.stats();
+        assert_eq!((stats.pooled, stats.discards), (1, 3));
+    }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod buffer_pool;
\ No newline at end of file
@@ -0,0 +1,23 @@
+use crate::Codec;
+use crate::Error;
+use bytes::Bytes;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BincodeCodec;
+
+impl<T> Codec<T> for BincodeCodec
+where
+    T: Serialize + DeserializeOwned,
+{
+    fn encode(&self, message: &T) -> Result<Bytes, Error> {
+        bincode::serialize(message)
+            .map(Bytes::from)
+            .map_err(|err| Error::Codec(err.to_string()))
+    }
+
+    fn decode(&self, bytes: Bytes) -> Result<T, Error> {
+        bincode::deserialize(bytes.as_ref()).map_err(|err| Error::Codec(err.to_string()))
+    }
+}
\ No newline at end of file
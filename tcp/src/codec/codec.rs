@@ -0,0 +1,7 @@
+use crate::Error;
+use bytes::Bytes;
+
+pub trait Codec<T> {
+    fn encode(&self, message: &T) -> Result<Bytes, Error>;
+    fn decode(&self, bytes: Bytes) -> Result<T, Error>;
+}
\ No newline at end of file
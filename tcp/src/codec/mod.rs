@@ -0,0 +1,5 @@
+pub mod codec;
+#[cfg(feature = "json")]
+pub mod json_codec;
+#[cfg(feature = "bincode")]
+pub mod bincode_codec;
\ No newline at end of file
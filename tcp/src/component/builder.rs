@@ -1,4 +1,7 @@
 use crate::Framer;
+use crate::FramerFactory;
+use crate::Layer;
+use crate::LayerFactory;
 use crate::Dispatcher;
 use crate::DefaultFramer;
 use crate::ListenerCreator;
@@ -9,6 +12,7 @@ use crate::ServerComponent;
 use crate::Listener;
 use crate::Socket;
 use std::sync::Arc;
+use std::time::Duration;
 
 const DEFAULT_LISTENER_LIMIT: usize = 0;
 const DEFAULT_SOCKET_LIMIT: usize = 256;
@@ -16,6 +20,13 @@ const DEFAULT_MESSAGE_LIMIT: usize = 512;
 const DEFAULT_EVENT_LIMIT: usize = 128;
 const DEFAULT_DISPATCH_LIMIT: usize = 8;
 const DEFAULT_SOCKET_EVENTS: usize = 64;
+const DEFAULT_FRAME_LIMIT: usize = 8 * 1024 * 1024;
+const DEFAULT_FRAGMENT_SIZE: usize = 0;
+const DEFAULT_REASSEMBLY_LIMIT: usize = 64 * 1024 * 1024;
+const DEFAULT_WRITE_BUDGET: usize = 64 * 1024;
+const DEFAULT_READ_BUFFER: usize = 4096;
+const DEFAULT_READ_BUFFER_MAX: usize = 1024 * 1024;
+const DEFAULT_LINGER: Duration = Duration::from_secs(5);
 
 pub struct Builder {    
     pub(crate) listeners: usize,
@@ -24,7 +35,21 @@ pub struct Builder {
     pub(crate) events: usize,
     pub(crate) dispatchs: usize,
     pub(crate) socket_events: usize,
-    pub(crate) framer: Arc<dyn Framer>,
+    pub(crate) frame_limit: usize,
+    pub(crate) fragment: usize,
+    pub(crate) reassembly_limit: usize,
+    pub(crate) write_budget: usize,
+    pub(crate) read_buffer: usize,
+    pub(crate) read_buffer_max: usize,
+    pub(crate) adaptive_read_buffer: bool,
+    pub(crate) pool_buffer: usize,
+    pub(crate) pool_capacity: usize,
+    pub(crate) linger: Duration,
+    pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) read_timeout: Option<Duration>,
+    pub(crate) write_timeout: Option<Duration>,
+    pub(crate) framer: Arc<dyn FramerFactory>,
+    pub(crate) layers: Vec<Arc<dyn LayerFactory>>,
     pub(crate) dispatcher: Option<&'static mut dyn Dispatcher>,
 }
 
@@ -38,7 +63,21 @@ impl Builder {
             events: DEFAULT_EVENT_LIMIT,
             dispatchs: DEFAULT_DISPATCH_LIMIT,
             socket_events: DEFAULT_SOCKET_EVENTS,
-            framer: Arc::new(DefaultFramer::default()),
+            frame_limit: DEFAULT_FRAME_LIMIT,
+            fragment: DEFAULT_FRAGMENT_SIZE,
+            reassembly_limit: DEFAULT_REASSEMBLY_LIMIT,
+            write_budget: DEFAULT_WRITE_BUDGET,
+            read_buffer: DEFAULT_READ_BUFFER,
+            read_buffer_max: DEFAULT_READ_BUFFER_MAX,
+            adaptive_read_buffer: false,
+            pool_buffer: DEFAULT_READ_BUFFER,
+            pool_capacity: 0,
+            linger: DEFAULT_LINGER,
+            idle_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+            framer: Arc::new(|| Box::new(DefaultFramer) as Box<dyn Framer>),
+            layers: Vec::new(),
             dispatcher: None,
         }
     }
@@ -80,8 +119,105 @@ impl Builder {
     }
 
     #[allow(dead_code)]
-    pub fn framer(mut self, framer: Arc<dyn Framer>) -> Self {
-        self.framer = framer;
+    pub fn frame_limit(mut self, n: usize) -> Self {
+        self.frame_limit = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn fragment(mut self, n: usize) -> Self {
+        self.fragment = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn reassembly_limit(mut self, n: usize) -> Self {
+        self.reassembly_limit = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn write_budget(mut self, n: usize) -> Self {
+        self.write_budget = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn read_buffer(mut self, n: usize) -> Self {
+        self.read_buffer = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn read_buffer_max(mut self, n: usize) -> Self {
+        self.read_buffer_max = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn adaptive_read_buffer(mut self, enable: bool) -> Self {
+        self.adaptive_read_buffer = enable;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn buffer_pool(mut self, size: usize, capacity: usize) -> Self {
+        self.pool_buffer = size;
+        self.pool_capacity = capacity;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn linger(mut self, timeout: Duration) -> Self {
+        self.linger = timeout;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = Some(timeout);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn framer<F>(mut self, framer: F) -> Self
+    where
+        F: Framer + Clone + Sync + 'static,
+    {
+        self.framer = Arc::new(move || Box::new(framer.clone()) as Box<dyn Framer>);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn framer_factory(mut self, factory: Arc<dyn FramerFactory>) -> Self {
+        self.framer = factory;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer + Clone + Sync + 'static,
+    {
+        self.layers.push(Arc::new(move || Box::new(layer.clone()) as Box<dyn Layer>));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn layer_factory(mut self, factory: Arc<dyn LayerFactory>) -> Self {
+        self.layers.push(factory);
         self
     }
 
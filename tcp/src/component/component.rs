@@ -1,4 +1,5 @@
 use crate::Error;
+use crate::BufferPool;
 use crate::Listener;
 use crate::ListenerCreator;
 use crate::Socket;
@@ -14,6 +15,7 @@ where
 {
     fn connect(&mut self, addr: SocketAddr) -> Result<Arc<dyn Socket>, Error>;
     fn dispatch(&mut self) -> bool;
+    fn buffer_pool(&self) -> Option<Arc<BufferPool>>;
     fn close(self);
 }
 
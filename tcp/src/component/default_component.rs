@@ -1,5 +1,7 @@
 use crate::Error;
-use crate::Framer;
+use crate::BufferPool;
+use crate::FramerFactory;
+use crate::Pipeline;
 use crate::Message;
 use crate::Dispatcher;
 use crate::Listener;
@@ -8,6 +10,7 @@ use crate::ListenerCreator;
 use crate::Socket;
 use crate::SocketBuilder;
 use crate::SocketCreator;
+use crate::SocketOptions;
 use crate::ServerComponent;
 use crate::Component;
 use crate::ComponentBuilder;
@@ -43,7 +46,8 @@ struct ThreadContext {
 pub struct DefaultComponent {
     dispatches: usize,
     socket_events: usize,
-    framer: Arc<dyn Framer>,
+    options: SocketOptions,
+    framer: Arc<dyn FramerFactory>,
     mtx: MpscSender<Message>,
     mrx: MpscReceiver<Message>,
     etx: MpscSender<Event>,
@@ -154,6 +158,10 @@ where
         DefaultComponent::dispatch(self)
     }
 
+    fn buffer_pool(&self) -> Option<Arc<BufferPool>> {
+        self.options.pool.clone()
+    }
+
     fn close(self) {
         DefaultComponent::close(self)
     }
@@ -192,8 +200,25 @@ where
 
         Self {
             socket_events: builder.socket_events,
+            options: SocketOptions {
+                frame_limit: builder.frame_limit,
+                fragment: builder.fragment,
+                reassembly: builder.reassembly_limit,
+                write_budget: builder.write_budget,
+                read_buffer: builder.read_buffer,
+                read_buffer_max: builder.read_buffer_max.max(builder.read_buffer),
+                adaptive: builder.adaptive_read_buffer,
+                pool: match builder.pool_capacity {
+                    0 => None,
+                    n => Some(Arc::new(BufferPool::new(builder.pool_buffer, n))),
+                },
+                linger: builder.linger,
+                idle_timeout: builder.idle_timeout,
+                read_timeout: builder.read_timeout,
+                write_timeout: builder.write_timeout,
+            },
             dispatches: builder.dispatchs,
-            framer: builder.framer,
+            framer: Pipeline::factory(builder.framer, builder.layers),
             mtx,
             mrx,
             etx,
@@ -212,6 +237,7 @@ impl DefaultComponent {
     {
         let builder = ListenerBuilder::new(
             self.socket_events,
+            self.options.clone(),
             addr, 
             self.framer.clone(), 
             self.mtx.clone(), 
@@ -235,6 +261,7 @@ impl DefaultComponent {
     {
         let builder = SocketBuilder::new(
             self.socket_events,
+            self.options.clone(),
             self.framer.clone(),
             self.mtx.clone(),
             self.stx.subscribe(),
@@ -276,6 +303,13 @@ impl DefaultComponent {
                                 },
                                 Message::ReceiveDone(socket, bytes) => {
                                     dispatcher.receive_done(socket, bytes);
+                                },
+                                Message::ReceiveHandshake(socket, bytes, resume) => {
+                                    dispatcher.receive_done(socket, bytes);
+                                    let _ = resume.send(());
+                                },
+                                Message::SendDropped(socket, frames) => {
+                                    dispatcher.send_dropped(socket, frames);
                                 }
                             }
                         },
@@ -11,4 +11,5 @@ pub trait Dispatcher {
     fn receive_done(&mut self, socket: Arc<dyn Socket>, bytes: Bytes);
     fn connect_abort(&mut self, socket: Arc<dyn Socket>, err: Error);
     fn connect_terminate(&mut self, socket: Arc<dyn Socket>);
+    fn send_dropped(&mut self, _socket: Arc<dyn Socket>, _frames: usize) {}
 }
\ No newline at end of file
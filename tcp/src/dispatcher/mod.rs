@@ -1 +1,3 @@
-pub mod dispatcher;
\ No newline at end of file
+pub mod dispatcher;
+pub mod typed_dispatcher;
+pub mod router;
\ No newline at end of file
@@ -0,0 +1,145 @@
+use crate::Dispatcher;
+use crate::Endian;
+use crate::Error;
+use crate::Listener;
+use crate::Socket;
+use std::mem;
+use std::sync::Arc;
+use std::collections::HashMap;
+use bytes::Bytes;
+
+type Handler<D> = Box<dyn FnMut(&mut D, Arc<dyn Socket>, Bytes)>;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RouteStats {
+    pub frames: u64,
+    pub bytes: u64,
+}
+
+struct Route<D> {
+    handler: Handler<D>,
+    stats: RouteStats,
+}
+
+pub struct Router<D> {
+    endian: Endian,
+    dispatcher: D,
+    routes: HashMap<u16, Route<D>>,
+    fallback: Option<Handler<D>>,
+    unknown: RouteStats,
+}
+
+impl RouteStats {
+    fn record(&mut self, bytes: &Bytes) {
+        self.frames += 1;
+        self.bytes += bytes.len() as u64;
+    }
+}
+
+impl<D> Router<D>
+where
+    D: Dispatcher,
+{
+    pub fn new(dispatcher: D) -> Self {
+        Self {
+            endian: Endian::Little,
+            dispatcher,
+            routes: HashMap::new(),
+            fallback: None,
+            unknown: RouteStats::default(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn route<F>(mut self, opcode: u16, handler: F) -> Self
+    where
+        F: FnMut(&mut D, Arc<dyn Socket>, Bytes) + 'static,
+    {
+        self.routes.insert(opcode, Route { handler: Box::new(handler), stats: RouteStats::default() });
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn fallback<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(&mut D, Arc<dyn Socket>, Bytes) + 'static,
+    {
+        self.fallback = Some(Box::new(handler));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn stats(&self, opcode: u16) -> Option<RouteStats> {
+        self.routes.get(&opcode).map(|route| route.stats)
+    }
+
+    #[allow(dead_code)]
+    pub fn unknown_stats(&self) -> RouteStats {
+        self.unknown
+    }
+
+    #[allow(dead_code)]
+    pub fn dispatcher(&mut self) -> &mut D {
+        &mut self.dispatcher
+    }
+
+    fn opcode(&self, bytes: &Bytes) -> Option<u16> {
+        let mut opcode = [0u8; mem::size_of::<u16>()];
+        opcode.copy_from_slice(bytes.get(..mem::size_of::<u16>())?);
+        match self.endian {
+            Endian::Big => Some(u16::from_be_bytes(opcode)),
+            Endian::Little => Some(u16::from_le_bytes(opcode)),
+        }
+    }
+}
+
+impl<D> Dispatcher for Router<D>
+where
+    D: Dispatcher,
+{
+    fn listen_fatal(&mut self, listener: Arc<dyn Listener>, err: Error) {
+        self.dispatcher.listen_fatal(listener, err);
+    }
+
+    fn connect_fatal(&mut self, socket: Arc<dyn Socket>, err: Error) {
+        self.dispatcher.connect_fatal(socket, err);
+    }
+
+    fn connect_done(&mut self, listener: Option<Arc<dyn Listener>>, socket: Arc<dyn Socket>) {
+        self.dispatcher.connect_done(listener, socket);
+    }
+
+    fn receive_done(&mut self, socket: Arc<dyn Socket>, bytes: Bytes) {
+        let route = self.opcode(&bytes).and_then(|opcode| self.routes.get_mut(&opcode));
+        if let Some(route) = route {
+            let payload = bytes.slice(mem::size_of::<u16>()..);
+            route.stats.record(&payload);
+            (route.handler)(&mut self.dispatcher, socket, payload);
+            return;
+        }
+
+        self.unknown.record(&bytes);
+        match self.fallback.as_mut() {
+            Some(fallback) => fallback(&mut self.dispatcher, socket, bytes),
+            None => self.dispatcher.receive_done(socket, bytes),
+        }
+    }
+
+    fn connect_abort(&mut self, socket: Arc<dyn Socket>, err: Error) {
+        self.dispatcher.connect_abort(socket, err);
+    }
+
+    fn connect_terminate(&mut self, socket: Arc<dyn Socket>) {
+        self.dispatcher.connect_terminate(socket);
+    }
+
+    fn send_dropped(&mut self, socket: Arc<dyn Socket>, frames: usize) {
+        self.dispatcher.send_dropped(socket, frames);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use crate::Codec;
+use crate::Dispatcher;
+use crate::Error;
+use crate::Listener;
+use crate::Socket;
+use std::sync::Arc;
+use std::marker::PhantomData;
+use bytes::Bytes;
+
+pub trait TypedDispatcher<T> {
+    fn listen_fatal(&mut self, listener: Arc<dyn Listener>, err: Error);
+    fn connect_fatal(&mut self, socket: Arc<dyn Socket>, err: Error);
+    fn connect_done(&mut self, listener: Option<Arc<dyn Listener>>, socket: Arc<dyn Socket>);
+    fn receive_message(&mut self, socket: Arc<dyn Socket>, message: T);
+    fn decode_fatal(&mut self, socket: Arc<dyn Socket>, err: Error);
+    fn connect_abort(&mut self, socket: Arc<dyn Socket>, err: Error);
+    fn connect_terminate(&mut self, socket: Arc<dyn Socket>);
+    fn send_dropped(&mut self, _socket: Arc<dyn Socket>, _frames: usize) {}
+}
+
+pub struct CodecDispatcher<T, C, D> {
+    codec: C,
+    dispatcher: D,
+    message: PhantomData<fn() -> T>,
+}
+
+impl<T, C, D> CodecDispatcher<T, C, D>
+where
+    C: Codec<T>,
+    D: TypedDispatcher<T>,
+{
+    pub fn new(codec: C, dispatcher: D) -> Self {
+        Self { codec, dispatcher, message: PhantomData }
+    }
+
+    #[allow(dead_code)]
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    #[allow(dead_code)]
+    pub fn dispatcher(&mut self) -> &mut D {
+        &mut self.dispatcher
+    }
+}
+
+impl<T, C, D> Dispatcher for CodecDispatcher<T, C, D>
+where
+    C: Codec<T>,
+    D: TypedDispatcher<T>,
+{
+    fn listen_fatal(&mut self, listener: Arc<dyn Listener>, err: Error) {
+        self.dispatcher.listen_fatal(listener, err);
+    }
+
+    fn connect_fatal(&mut self, socket: Arc<dyn Socket>, err: Error) {
+        self.dispatcher.connect_fatal(socket, err);
+    }
+
+    fn connect_done(&mut self, listener: Option<Arc<dyn Listener>>, socket: Arc<dyn Socket>) {
+        self.dispatcher.connect_done(listener, socket);
+    }
+
+    fn receive_done(&mut self, socket: Arc<dyn Socket>, bytes: Bytes) {
+        match self.codec.decode(bytes) {
+            Ok(message) => self.dispatcher.receive_message(socket, message),
+            Err(err) => self.dispatcher.decode_fatal(socket, err),
+        }
+    }
+
+    fn connect_abort(&mut self, socket: Arc<dyn Socket>, err: Error) {
+        self.dispatcher.connect_abort(socket, err);
+    }
+
+    fn connect_terminate(&mut self, socket: Arc<dyn Socket>) {
+        self.dispatcher.connect_terminate(socket);
+    }
+
+    fn send_dropped(&mut self, socket: Arc<dyn Socket>, frames: usize) {
+        self.dispatcher.send_dropped(socket, frames);
+    }
+}
\ No newline at end of file
@@ -5,6 +5,13 @@ use std::error::Error as StdError;
 pub enum Error {
     Module(String),
     System(String),
+    Protocol(String),
+    Oversize(String),
+    Codec(String),
+    QueueFull(String),
+    IdleTimeout(String),
+    ReadTimeout(String),
+    WriteTimeout(String),
 }
 
 impl Display for Error {
@@ -12,6 +19,13 @@ impl Display for Error {
         match self {
             Error::Module(s) => write!(f, "Error: {{ Module: {} }}", s),
             Error::System(s) => write!(f, "Error: {{ System: {} }}", s),
+            Error::Protocol(s) => write!(f, "Error: {{ Protocol: {} }}", s),
+            Error::Oversize(s) => write!(f, "Error: {{ Oversize: {} }}", s),
+            Error::Codec(s) => write!(f, "Error: {{ Codec: {} }}", s),
+            Error::QueueFull(s) => write!(f, "Error: {{ QueueFull: {} }}", s),
+            Error::IdleTimeout(s) => write!(f, "Error: {{ IdleTimeout: {} }}", s),
+            Error::ReadTimeout(s) => write!(f, "Error: {{ ReadTimeout: {} }}", s),
+            Error::WriteTimeout(s) => write!(f, "Error: {{ WriteTimeout: {} }}", s),
         }
     }
 }
@@ -28,7 +42,10 @@ impl<T> From<tokio::sync::mpsc::error::SendError<T>> for Error {
 
 impl<T> From<tokio::sync::mpsc::error::TrySendError<T>> for Error {
     fn from(value: tokio::sync::mpsc::error::TrySendError<T>) -> Self {
-        Error::Module(value.to_string())
+        match value {
+            tokio::sync::mpsc::error::TrySendError::Full(_) => Error::QueueFull(value.to_string()),
+            tokio::sync::mpsc::error::TrySendError::Closed(_) => Error::Module(value.to_string()),
+        }
     }
 }
 
@@ -0,0 +1,78 @@
+use crate::Error;
+use crate::Frame;
+use crate::Framer;
+use crate::LengthPrefixed;
+use std::mem;
+use std::io::Cursor;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+const CHECKSUM_LEN: usize = mem::size_of::<u32>();
+
+/// The CRC covers the payload handed to the inner framer and is verified after
+/// the inner framer has decoded the frame, skipping any header it retains.
+#[derive(Debug, Clone)]
+pub struct ChecksumFramer<F> {
+    inner: F,
+}
+
+impl<F> ChecksumFramer<F>
+where
+    F: LengthPrefixed,
+{
+    pub fn new(inner: F) -> Self {
+        Self { inner }
+    }
+}
+
+impl<F> Framer for ChecksumFramer<F>
+where
+    F: LengthPrefixed,
+{
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame {
+        match self.inner.parse(cursor) {
+            Frame::Success(_, payload, _) if payload < CHECKSUM_LEN => Frame::Fatal,
+            frame => frame,
+        }
+    }
+
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let mut buf = BytesMut::with_capacity(bytes.len() + CHECKSUM_LEN);
+        buf.put(bytes.as_ref());
+        buf.put_u32_le(crc32fast::hash(bytes.as_ref()));
+        self.inner.encode(buf.freeze())
+    }
+
+    fn decode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let mut bytes = self.inner.decode(bytes)?;
+        if bytes.len() < CHECKSUM_LEN {
+            return Err(Error::Protocol(String::from("missing checksum trailer")));
+        }
+
+        let checksum = bytes.split_off(bytes.len() - CHECKSUM_LEN);
+        let checksum = u32::from_le_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+        let retained = self.inner.retained().min(bytes.len());
+        if crc32fast::hash(&bytes[retained..]) != checksum {
+            return Err(Error::Protocol(String::from("checksum mismatch")));
+        }
+        Ok(bytes)
+    }
+
+    fn keep_header(&self) -> bool {
+        self.inner.keep_header()
+    }
+
+    fn handshake(&self) -> bool {
+        self.inner.handshake()
+    }
+}
+
+impl<F> LengthPrefixed for ChecksumFramer<F>
+where
+    F: LengthPrefixed,
+{
+    fn retained(&self) -> usize {
+        self.inner.retained()
+    }
+}
\ No newline at end of file
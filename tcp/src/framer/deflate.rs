@@ -0,0 +1,211 @@
+use crate::Error;
+use crate::Layer;
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+use miniz_oxide::deflate::compress_to_vec;
+use miniz_oxide::inflate::decompress_to_vec_with_limit;
+
+const FLAG_COMPRESSED: u8 = 0x01;
+const FLAG_HELLO: u8 = 0x02;
+const FLAG_ACCEPT: u8 = 0x04;
+const DEFAULT_THRESHOLD: usize = 256;
+const DEFAULT_LEVEL: u8 = 6;
+const DEFAULT_LIMIT: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct Deflate {
+    threshold: usize,
+    level: u8,
+    limit: usize,
+}
+
+/// Pipeline stage that works over any framer. Each frame gets a flag byte;
+/// the first one a side sends carries `FLAG_HELLO` and whether it accepts
+/// compressed frames, and nothing is compressed until the peer accepted.
+#[derive(Debug, Clone)]
+pub struct DeflateLayer {
+    deflate: Deflate,
+    accept: bool,
+    greeted: bool,
+    peer: Option<bool>,
+}
+
+impl Default for Deflate {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_THRESHOLD,
+            level: DEFAULT_LEVEL,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl Deflate {
+    #[allow(dead_code)]
+    pub fn threshold(mut self, n: usize) -> Self {
+        self.threshold = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn level(mut self, n: u8) -> Self {
+        self.level = n.min(10);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = n;
+        self
+    }
+
+    pub(crate) fn compress(&self, bytes: &Bytes) -> Option<Bytes> {
+        if bytes.len() < self.threshold {
+            return None;
+        }
+
+        let compressed = compress_to_vec(bytes.as_ref(), self.level);
+        match compressed.len() < bytes.len() {
+            true => Some(Bytes::from(compressed)),
+            false => None,
+        }
+    }
+
+    pub(crate) fn decompress(&self, bytes: &[u8]) -> Result<Bytes, Error> {
+        decompress_to_vec_with_limit(bytes, self.limit)
+            .map(Bytes::from)
+            .map_err(|err| Error::Protocol(format!("failed to decompress the frame: {}", err)))
+    }
+}
+
+impl DeflateLayer {
+    pub fn new(deflate: Deflate) -> Self {
+        Self { deflate, accept: true, greeted: false, peer: None }
+    }
+
+    #[allow(dead_code)]
+    pub fn accept(mut self, accept: bool) -> Self {
+        self.accept = accept;
+        self
+    }
+}
+
+impl Layer for DeflateLayer {
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let mut flag = 0;
+        if !self.greeted {
+            self.greeted = true;
+            flag |= FLAG_HELLO;
+            if self.accept {
+                flag |= FLAG_ACCEPT;
+            }
+        }
+
+        let compressed = match self.peer {
+            Some(true) => self.deflate.compress(&bytes),
+            _ => None,
+        };
+        if compressed.is_some() {
+            flag |= FLAG_COMPRESSED;
+        }
+
+        let payload = compressed.unwrap_or(bytes);
+        let mut buf = BytesMut::with_capacity(1 + payload.len());
+        buf.put_u8(flag);
+        buf.put(payload);
+        Ok(buf.freeze())
+    }
+
+    fn decode(&mut self, mut bytes: Bytes) -> Result<Bytes, Error> {
+        if bytes.is_empty() {
+            return Err(Error::Protocol(String::from("missing compression flag")));
+        }
+
+        let flag = bytes.get_u8();
+        if 0 != flag & !(FLAG_COMPRESSED | FLAG_HELLO | FLAG_ACCEPT) {
+            return Err(Error::Protocol(format!("unknown compression flag {:#04x}", flag)));
+        }
+
+        let hello = 0 != flag & FLAG_HELLO;
+        match (hello, self.peer) {
+            (true, None) => self.peer = Some(0 != flag & FLAG_ACCEPT),
+            (false, None) => return Err(Error::Protocol(String::from("peer did not negotiate compression"))),
+            (true, Some(_)) => return Err(Error::Protocol(String::from("peer negotiated compression twice"))),
+            (false, Some(_)) => (),
+        }
+
+        if 0 == flag & FLAG_COMPRESSED {
+            return Ok(bytes);
+        }
+
+        if !self.accept {
+            return Err(Error::Protocol(String::from("peer compressed a frame that was not accepted")));
+        }
+        self.deflate.decompress(&bytes)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn large() -> Bytes {
+        Bytes::from(vec![b'a'; 1024])
+    }
+
+    #[test]
+    fn compresses_only_after_the_peer_accepted() {
+        let mut client = DeflateLayer::new(Deflate::default());
+        let mut server = DeflateLayer::new(Deflate::default());
+
+        let hello = client.encode(large()).unwrap();
+        assert_eq!(hello[0], FLAG_HELLO | FLAG_ACCEPT);
+        assert_eq!(hello.len(), 1 + 1024);
+        assert_eq!(server.decode(hello).unwrap(), large());
+
+        let reply = server.encode(large()).unwrap();
+        assert_eq!(reply[0], FLAG_HELLO | FLAG_ACCEPT | FLAG_COMPRESSED);
+        assert!(reply.len() < 1024);
+        assert_eq!(client.decode(reply).unwrap(), large());
+
+        let next = client.encode(large()).unwrap();
+        assert_eq!(next[0], FLAG_COMPRESSED);
+        assert_eq!(server.decode(next).unwrap(), large());
+
+        let small = client.encode(Bytes::from_static(b"hi")).unwrap();
+        assert_eq!(&small[..], &[0, b'h', b'i']);
+        assert_eq!(&server.decode(small).unwrap()[..], b"hi");
+    }
+
+    #[test]
+    fn never_compresses_for_a_peer_that_refused() {
+        let mut client = DeflateLayer::new(Deflate::default()).accept(false);
+        let mut server = DeflateLayer::new(Deflate::default());
+
+        let hello = client.encode(Bytes::from_static(b"hi")).unwrap();
+        assert_eq!(hello[0], FLAG_HELLO);
+        server.decode(hello).unwrap();
+
+        let reply = server.encode(large()).unwrap();
+        assert_eq!(reply[0], FLAG_HELLO | FLAG_ACCEPT);
+        assert_eq!(client.decode(reply).unwrap(), large());
+    }
+
+    #[test]
+    fn rejects_frames_outside_the_negotiation() {
+        let mut layer = DeflateLayer::new(Deflate::default());
+        assert!(matches!(layer.decode(Bytes::new()), Err(Error::Protocol(_))));
+        assert!(matches!(layer.decode(Bytes::from_static(&[0, 1])), Err(Error::Protocol(_))));
+        assert!(matches!(layer.decode(Bytes::from_static(&[0x80, 1])), Err(Error::Protocol(_))));
+
+        layer.decode(Bytes::from_static(&[FLAG_HELLO, 1])).unwrap();
+        assert!(matches!(layer.decode(Bytes::from_static(&[FLAG_HELLO, 1])), Err(Error::Protocol(_))));
+
+        let mut refusing = DeflateLayer::new(Deflate::default()).accept(false);
+        let compressed = Bytes::from_static(&[FLAG_HELLO | FLAG_COMPRESSED, 1]);
+        assert!(matches!(refusing.decode(compressed), Err(Error::Protocol(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,136 @@
+use crate::Error;
+use crate::Frame;
+use crate::Framer;
+use std::io::Cursor;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+const DEFAULT_LINE_LIMIT: usize = 8 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct DelimiterFramer {
+    delimiter: Bytes,
+    limit: usize,
+    strip: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct LinesFramer {
+    limit: usize,
+    strip: bool,
+    crlf: bool,
+}
+
+impl DelimiterFramer {
+    pub fn new(delimiter: Bytes) -> Self {
+        assert!(!delimiter.is_empty(), "delimiter must not be empty");
+        Self {
+            delimiter,
+            limit: DEFAULT_LINE_LIMIT,
+            strip: true,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn strip(mut self, strip: bool) -> Self {
+        self.strip = strip;
+        self
+    }
+}
+
+impl Framer for DelimiterFramer {
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame {
+        let buf = cursor.get_ref();
+        let delimiter = self.delimiter.as_ref();
+        let found = buf
+            .windows(delimiter.len())
+            .take(self.limit + 1)
+            .position(|window| window == delimiter);
+
+        match found {
+            Some(pos) if self.strip => Frame::Success(0, pos, delimiter.len()),
+            Some(pos) => Frame::Success(0, pos + delimiter.len(), 0),
+            None if buf.len() >= self.limit + delimiter.len() => Frame::Fatal,
+            None => Frame::Continue,
+        }
+    }
+
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let mut buf = BytesMut::with_capacity(bytes.len() + self.delimiter.len());
+        buf.put(bytes);
+        buf.put(self.delimiter.as_ref());
+        Ok(buf.freeze())
+    }
+}
+
+impl Default for LinesFramer {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_LINE_LIMIT,
+            strip: true,
+            crlf: false,
+        }
+    }
+}
+
+impl LinesFramer {
+    #[allow(dead_code)]
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn strip(mut self, strip: bool) -> Self {
+        self.strip = strip;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn crlf(mut self, crlf: bool) -> Self {
+        self.crlf = crlf;
+        self
+    }
+}
+
+impl Framer for LinesFramer {
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame {
+        let buf = cursor.get_ref();
+        let found = buf
+            .iter()
+            .take(self.limit + 2)
+            .position(|byte| *byte == b'\n');
+
+        match found {
+            Some(pos) => {
+                let cr = (pos > 0 && buf[pos - 1] == b'\r') as usize;
+                if pos - cr > self.limit {
+                    Frame::Fatal
+                } else if self.strip {
+                    Frame::Success(0, pos - cr, cr + 1)
+                } else {
+                    Frame::Success(0, pos + 1, 0)
+                }
+            },
+            None if buf.len() >= self.limit + 2 => Frame::Fatal,
+            None => Frame::Continue,
+        }
+    }
+
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let mut buf = BytesMut::with_capacity(bytes.len() + 2);
+        buf.put(bytes);
+        if self.crlf {
+            buf.put_u8(b'\r');
+        }
+        buf.put_u8(b'\n');
+        Ok(buf.freeze())
+    }
+}
\ No newline at end of file
@@ -1,44 +1,109 @@
 use crate::Error;
 use std::mem;
-use std::sync::Arc;
 use std::io::Cursor;
 use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
 use bytes::BytesMut;
 
 pub enum Frame {
     Fatal,
     Continue,
-    Success(u32),
+    Partial(usize),
+    Success(usize, usize, usize),
 }
 
-pub trait Framer: Send + Sync {
-    fn parse(self: Arc<Self>, cursor: &mut Cursor<&BytesMut>) -> Frame;
-    fn check(self: Arc<Self>, cursor: &mut Cursor<&BytesMut>) -> Result<u32, Error> {
+pub trait Framer: Send {
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame;
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error>;
+    fn decode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        Ok(bytes)
+    }
+    fn keep_header(&self) -> bool {
+        false
+    }
+    fn handshake(&self) -> bool {
+        false
+    }
+    /// Called by `Socket::set_framer`; wrappers that own stages of their own
+    /// keep them and swap only the framer they wrap.
+    fn replace(self: Box<Self>, framer: Box<dyn Framer>) -> Box<dyn Framer> {
+        framer
+    }
+    fn check(&mut self, cursor: &mut Cursor<&BytesMut>, 
+        limit: usize) -> Result<Frame, Error> {
         match self.parse(cursor) {
-            Frame::Success(len) => Ok(len),
-            Frame::Continue => Ok(0),
+            Frame::Success(header, payload, trailer) if header + payload + trailer > limit => Err(
+                Error::Oversize(format!(
+                    "frame of {} bytes exceeds the limit of {}", header + payload + trailer, limit
+                ))
+            ),
+            Frame::Partial(size) if size > limit => Err(
+                Error::Oversize(format!("frame of {} bytes exceeds the limit of {}", size, limit))
+            ),
             Frame::Fatal => Err(
-                Error::Module(String::from("a fatal error occurred while parsing the frame"))
+                Error::Protocol(String::from("a fatal error occurred while parsing the frame"))
             ),
+            frame => Ok(frame),
         }
     }
 }
 
-#[derive(Default)]
+/// Framers whose frames are delimited by a length header rather than by
+/// scanning the payload, so binary trailers can never split a frame.
+pub trait LengthPrefixed: Framer {
+    fn retained(&self) -> usize {
+        0
+    }
+}
+
+pub trait FramerFactory: Send + Sync {
+    fn create(&self) -> Box<dyn Framer>;
+}
+
+impl<F> FramerFactory for F
+where
+    F: Fn() -> Box<dyn Framer> + Send + Sync,
+{
+    fn create(&self) -> Box<dyn Framer> {
+        self()
+    }
+}
+
+#[derive(Default, Clone)]
 pub(crate) struct DefaultFramer;
 
 impl Framer for DefaultFramer {
-    fn parse(self: Arc<Self>, cursor: &mut Cursor<&BytesMut>) -> Frame {
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame {
         let len = cursor.get_ref().len();
         if len < mem::size_of::<u32>() {
             return Frame::Continue;
         }
     
+        let header = mem::size_of::<u32>();
         let size = cursor.get_u32_le() as usize;
+        if size < header {
+            return Frame::Fatal;
+        }
+
         if len < size {
-            return Frame::Continue;
+            return Frame::Partial(size);
         }
     
-        Frame::Success(size as u32)
+        Frame::Success(header, size - header, 0)
     }
-}
\ No newline at end of file
+
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let size = mem::size_of::<u32>() + bytes.len();
+        if size > u32::MAX as usize {
+            return Err(Error::Module(String::from("the frame is too large to encode")));
+        }
+
+        let mut buf = BytesMut::with_capacity(size);
+        buf.put_u32_le(size as u32);
+        buf.put(bytes);
+        Ok(buf.freeze())
+    }
+}
+
+impl LengthPrefixed for DefaultFramer {}
\ No newline at end of file
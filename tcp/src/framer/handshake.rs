@@ -0,0 +1,53 @@
+use crate::Error;
+use crate::Frame;
+use crate::Framer;
+use std::io::Cursor;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+/// Wraps the framer used before a protocol upgrade. The reader hands every
+/// frame it parses to the dispatcher before parsing the next one, so a
+/// `set_framer` call from `receive_done` applies to the bytes that follow.
+#[derive(Debug, Clone)]
+pub struct Handshake<F> {
+    inner: F,
+}
+
+impl<F> Handshake<F>
+where
+    F: Framer,
+{
+    pub fn new(inner: F) -> Self {
+        Self { inner }
+    }
+}
+
+impl<F> Framer for Handshake<F>
+where
+    F: Framer,
+{
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame {
+        self.inner.parse(cursor)
+    }
+
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        self.inner.encode(bytes)
+    }
+
+    fn decode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        self.inner.decode(bytes)
+    }
+
+    fn keep_header(&self) -> bool {
+        self.inner.keep_header()
+    }
+
+    fn check(&mut self, cursor: &mut Cursor<&BytesMut>, 
+        limit: usize) -> Result<Frame, Error> {
+        self.inner.check(cursor, limit)
+    }
+
+    fn handshake(&self) -> bool {
+        true
+    }
+}
\ No newline at end of file
@@ -0,0 +1,300 @@
+use crate::Error;
+use crate::Frame;
+use crate::Framer;
+use crate::LengthPrefixed;
+#[cfg(feature = "deflate")]
+use crate::Deflate;
+use std::io::Cursor;
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+/// Frame length on the wire is `offset + width + adjustment + value`. With
+/// `deflate` the top bit of the length field marks a compressed payload; both
+/// ends must enable it, whereas `DeflateLayer` negotiates over any framer.
+#[derive(Debug, Clone)]
+pub struct LengthFieldFramer {
+    offset: usize,
+    width: usize,
+    endian: Endian,
+    adjustment: isize,
+    strip: Option<usize>,
+    prefix: Bytes,
+    #[cfg(feature = "deflate")]
+    deflate: Option<Deflate>,
+    #[cfg(feature = "deflate")]
+    compressed: bool,
+}
+
+impl Default for LengthFieldFramer {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            width: 4,
+            endian: Endian::Little,
+            adjustment: 0,
+            strip: None,
+            prefix: Bytes::new(),
+            #[cfg(feature = "deflate")]
+            deflate: None,
+            #[cfg(feature = "deflate")]
+            compressed: false,
+        }
+    }
+}
+
+impl LengthFieldFramer {
+    #[allow(dead_code)]
+    pub fn offset(mut self, n: usize) -> Self {
+        self.offset = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn width(mut self, n: usize) -> Self {
+        assert!(matches!(n, 1 | 2 | 3 | 4 | 8), "unsupported length field width: {}", n);
+        self.width = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn adjustment(mut self, n: isize) -> Self {
+        self.adjustment = n;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn strip(mut self, n: usize) -> Self {
+        self.strip = Some(n);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn prefix(mut self, prefix: Bytes) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    #[cfg(feature = "deflate")]
+    #[allow(dead_code)]
+    pub fn deflate(mut self, deflate: Deflate) -> Self {
+        self.deflate = Some(deflate);
+        self
+    }
+
+    fn header(&self) -> usize {
+        self.offset + self.width
+    }
+
+    fn flag(&self) -> u64 {
+        #[cfg(feature = "deflate")]
+        if self.deflate.is_some() {
+            return 1 << (self.width * 8 - 1);
+        }
+        0
+    }
+
+    #[cfg(feature = "deflate")]
+    fn compress(&self, bytes: Bytes) -> (Bytes, u64) {
+        match self.deflate.as_ref().and_then(|deflate| deflate.compress(&bytes)) {
+            Some(compressed) => (compressed, self.flag()),
+            None => (bytes, 0),
+        }
+    }
+
+    #[cfg(not(feature = "deflate"))]
+    fn compress(&self, bytes: Bytes) -> (Bytes, u64) {
+        (bytes, 0)
+    }
+}
+
+impl Framer for LengthFieldFramer {
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame {
+        let len = cursor.get_ref().len();
+        let header = self.header();
+        if len < header {
+            return Frame::Continue;
+        }
+
+        cursor.set_position(self.offset as u64);
+        let value = match self.endian {
+            Endian::Big => cursor.get_uint(self.width),
+            Endian::Little => cursor.get_uint_le(self.width),
+        };
+        #[cfg(feature = "deflate")]
+        let compressed = 0 != value & self.flag();
+        let value = value & !self.flag();
+
+        let size = (header as i128) + (self.adjustment as i128) + (value as i128);
+        if size < header as i128 || size > usize::MAX as i128 {
+            return Frame::Fatal;
+        }
+
+        let size = size as usize;
+        let strip = self.strip.unwrap_or(header);
+        if strip > size {
+            return Frame::Fatal;
+        }
+
+        if len < size {
+            return Frame::Partial(size);
+        }
+
+        #[cfg(feature = "deflate")]
+        {
+            self.compressed = compressed;
+        }
+
+        Frame::Success(strip, size - strip, 0)
+    }
+
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let (bytes, flag) = self.compress(bytes);
+        let value = (bytes.len() as i128) - (self.adjustment as i128);
+        let max = match self.flag() {
+            0 if self.width == 8 => u64::MAX as i128,
+            0 => (1i128 << (self.width * 8)) - 1,
+            reserved => reserved as i128 - 1,
+        };
+        if value < 0 || value > max {
+            return Err(Error::Module(String::from("the frame length does not fit the length field")));
+        }
+
+        let mut buf = BytesMut::with_capacity(self.header() + bytes.len());
+        let prefix = self.prefix.len().min(self.offset);
+        buf.put(&self.prefix[..prefix]);
+        buf.put_bytes(0, self.offset - prefix);
+        match self.endian {
+            Endian::Big => buf.put_uint(value as u64 | flag, self.width),
+            Endian::Little => buf.put_uint_le(value as u64 | flag, self.width),
+        }
+        buf.put(bytes);
+        Ok(buf.freeze())
+    }
+
+    #[cfg(feature = "deflate")]
+    fn decode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let deflate = match self.deflate.as_ref() {
+            Some(deflate) if std::mem::take(&mut self.compressed) => deflate,
+            _ => return Ok(bytes),
+        };
+
+        let retained = self.retained().min(bytes.len());
+        let payload = deflate.decompress(&bytes[retained..])?;
+        if 0 == retained {
+            return Ok(payload);
+        }
+
+        let mut buf = BytesMut::with_capacity(retained + payload.len());
+        buf.put(&bytes[..retained]);
+        buf.put(payload);
+        Ok(buf.freeze())
+    }
+}
+
+impl LengthPrefixed for LengthFieldFramer {
+    fn retained(&self) -> usize {
+        self.header().saturating_sub(self.strip.unwrap_or(self.header()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultFramer;
+
+    fn parse(framer: &mut LengthFieldFramer, bytes: &[u8]) -> Frame {
+        let buf = BytesMut::from(bytes);
+        framer.parse(&mut Cursor::new(&buf))
+    }
+
+    #[test]
+    fn widths_and_endians() {
+        for width in [1, 2, 3, 4, 8] {
+            for endian in [Endian::Big, Endian::Little] {
+                let mut framer = LengthFieldFramer::default().width(width).endian(endian);
+                let frame = framer.encode(Bytes::from_static(b"hello")).unwrap();
+
+                let mut header = vec![0; width];
+                match endian {
+                    Endian::Big => header[width - 1] = 5,
+                    Endian::Little => header[0] = 5,
+                }
+                assert_eq!(&frame[..width], &header[..]);
+                assert_eq!(&frame[width..], b"hello");
+                assert!(matches!(parse(&mut framer, &frame), Frame::Success(w, 5, 0) if w == width));
+            }
+        }
+    }
+
+    #[test]
+    fn offset_with_prefix_magic() {
+        let mut framer = LengthFieldFramer::default()
+            .offset(2)
+            .width(2)
+            .endian(Endian::Big)
+            .prefix(Bytes::from_static(b"\xca\xfe"));
+        let frame = framer.encode(Bytes::from_static(b"hi")).unwrap();
+        assert_eq!(&frame[..], b"\xca\xfe\x00\x02hi");
+
+        assert!(matches!(parse(&mut framer, &frame[..3]), Frame::Continue));
+        assert!(matches!(parse(&mut framer, &frame[..5]), Frame::Partial(6)));
+        assert!(matches!(parse(&mut framer, &frame), Frame::Success(4, 2, 0)));
+    }
+
+    #[test]
+    fn negative_adjustment_matches_the_default_framer() {
+        let mut framer = LengthFieldFramer::default().adjustment(-4);
+        let frame = framer.encode(Bytes::from_static(b"ab")).unwrap();
+        assert_eq!(frame, DefaultFramer.encode(Bytes::from_static(b"ab")).unwrap());
+        assert_eq!(&frame[..4], &[6, 0, 0, 0]);
+        assert!(matches!(parse(&mut framer, &frame), Frame::Success(4, 2, 0)));
+    }
+
+    #[test]
+    fn strip_smaller_than_the_header() {
+        let mut framer = LengthFieldFramer::default()
+            .offset(2)
+            .width(2)
+            .endian(Endian::Big)
+            .strip(2);
+        assert_eq!(framer.retained(), 2);
+        assert!(matches!(parse(&mut framer, b"\xca\xfe\x00\x02hi"), Frame::Success(2, 4, 0)));
+
+        let mut framer = LengthFieldFramer::default().strip(0);
+        assert_eq!(framer.retained(), 4);
+        assert!(matches!(parse(&mut framer, b"\x02\0\0\0hi"), Frame::Success(0, 6, 0)));
+    }
+
+    #[test]
+    fn fatal_headers() {
+        let mut framer = LengthFieldFramer::default().adjustment(-4);
+        assert!(matches!(parse(&mut framer, b"\x02\0\0\0"), Frame::Fatal));
+
+        let mut framer = LengthFieldFramer::default().strip(8);
+        assert!(matches!(parse(&mut framer, b"\x01\0\0\0x"), Frame::Fatal));
+    }
+
+    #[test]
+    fn length_must_fit_the_field() {
+        let mut framer = LengthFieldFramer::default().width(1);
+        assert!(framer.encode(Bytes::from(vec![0; 255])).is_ok());
+        assert!(framer.encode(Bytes::from(vec![0; 256])).is_err());
+
+        let mut framer = LengthFieldFramer::default().adjustment(4);
+        assert!(framer.encode(Bytes::from_static(b"abc")).is_err());
+    }
+}
\ No newline at end of file
@@ -1 +1,9 @@
-pub mod framer;
\ No newline at end of file
+pub mod framer;
+pub mod length_field_framer;
+pub mod delimiter_framer;
+pub mod varint_framer;
+pub mod checksum_framer;
+pub mod pipeline;
+pub mod handshake;
+#[cfg(feature = "deflate")]
+pub mod deflate;
\ No newline at end of file
@@ -0,0 +1,130 @@
+use crate::Error;
+use crate::Frame;
+use crate::Framer;
+use crate::FramerFactory;
+use std::sync::Arc;
+use std::io::Cursor;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+pub trait Layer: Send {
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error>;
+    fn decode(&mut self, bytes: Bytes) -> Result<Bytes, Error>;
+}
+
+pub trait LayerFactory: Send + Sync {
+    fn create(&self) -> Box<dyn Layer>;
+}
+
+impl<F> LayerFactory for F
+where
+    F: Fn() -> Box<dyn Layer> + Send + Sync,
+{
+    fn create(&self) -> Box<dyn Layer> {
+        self()
+    }
+}
+
+/// Layers encode in the order they were added and decode in reverse, so the
+/// first layer sits closest to the application.
+pub struct Pipeline {
+    framer: Box<dyn Framer>,
+    layers: Vec<Box<dyn Layer>>,
+}
+
+impl Pipeline {
+    pub fn new(framer: Box<dyn Framer>) -> Self {
+        Self { framer, layers: Vec::new() }
+    }
+
+    #[allow(dead_code)]
+    pub fn layer(mut self, layer: Box<dyn Layer>) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    pub(crate) fn factory(framer: Arc<dyn FramerFactory>, 
+        layers: Vec<Arc<dyn LayerFactory>>) -> Arc<dyn FramerFactory> {
+        if layers.is_empty() {
+            return framer;
+        }
+
+        Arc::new(move || {
+            let pipeline = layers.iter().fold(
+                Pipeline::new(framer.create()),
+                |pipeline, layer| pipeline.layer(layer.create())
+            );
+            Box::new(pipeline) as Box<dyn Framer>
+        })
+    }
+}
+
+impl Framer for Pipeline {
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame {
+        self.framer.parse(cursor)
+    }
+
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let bytes = self.layers
+            .iter_mut()
+            .try_fold(bytes, |bytes, layer| layer.encode(bytes))?;
+        self.framer.encode(bytes)
+    }
+
+    fn decode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let bytes = self.framer.decode(bytes)?;
+        self.layers
+            .iter_mut()
+            .rev()
+            .try_fold(bytes, |bytes, layer| layer.decode(bytes))
+    }
+
+    fn keep_header(&self) -> bool {
+        self.framer.keep_header()
+    }
+
+    fn handshake(&self) -> bool {
+        self.framer.handshake()
+    }
+
+    fn replace(mut self: Box<Self>, framer: Box<dyn Framer>) -> Box<dyn Framer> {
+        self.framer = framer;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultFramer;
+    use crate::LinesFramer;
+
+    struct Xor;
+
+    impl Layer for Xor {
+        fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+            Ok(bytes.iter().map(|byte| byte ^ 0xff).collect())
+        }
+
+        fn decode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+            self.encode(bytes)
+        }
+    }
+
+    #[test]
+    fn replace_keeps_layers() {
+        let pipeline = Pipeline::new(Box::new(LinesFramer::default())).layer(Box::new(Xor));
+        let mut framer = (Box::new(pipeline) as Box<dyn Framer>).replace(Box::new(DefaultFramer));
+
+        let frame = framer.encode(Bytes::from_static(b"ab")).unwrap();
+        assert_eq!(&frame[..], &[6, 0, 0, 0, !b'a', !b'b']);
+        assert_eq!(&framer.decode(frame.slice(4..)).unwrap()[..], b"ab");
+    }
+
+    #[test]
+    fn replace_without_layers_swaps_the_framer() {
+        let framer = Box::new(LinesFramer::default()) as Box<dyn Framer>;
+        let mut framer = framer.replace(Box::new(DefaultFramer));
+        assert_eq!(&framer.encode(Bytes::from_static(b"ab")).unwrap()[..], &[6, 0, 0, 0, b'a', b'b']);
+    }
+}
\ No newline at end of file
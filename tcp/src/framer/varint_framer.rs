@@ -0,0 +1,68 @@
+use crate::Error;
+use crate::Frame;
+use crate::Framer;
+use crate::LengthPrefixed;
+use std::io::Cursor;
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+const MAX_VARINT_LEN: usize = 10;
+
+#[derive(Debug, Clone, Default)]
+pub struct VarintFramer;
+
+impl Framer for VarintFramer {
+    fn parse(&mut self, cursor: &mut Cursor<&BytesMut>) -> Frame {
+        let mut value = 0u64;
+        let mut header = 0;
+
+        loop {
+            if !cursor.has_remaining() {
+                return Frame::Continue;
+            }
+
+            let byte = cursor.get_u8();
+            if header == MAX_VARINT_LEN - 1 && byte > 1 {
+                return Frame::Fatal;
+            }
+
+            value |= ((byte & 0x7f) as u64) << (7 * header);
+            header += 1;
+            if byte & 0x80 == 0 {
+                break;
+            }
+
+            if header == MAX_VARINT_LEN {
+                return Frame::Fatal;
+            }
+        }
+
+        let len = cursor.get_ref().len();
+        let size = match usize::try_from(value).ok().and_then(|value| value.checked_add(header)) {
+            Some(size) => size,
+            None => return Frame::Fatal,
+        };
+
+        if len < size {
+            return Frame::Partial(size);
+        }
+
+        Frame::Success(header, size - header, 0)
+    }
+
+    fn encode(&mut self, bytes: Bytes) -> Result<Bytes, Error> {
+        let mut buf = BytesMut::with_capacity(MAX_VARINT_LEN + bytes.len());
+        let mut value = bytes.len() as u64;
+        while value >= 0x80 {
+            buf.put_u8((value as u8) | 0x80);
+            value >>= 7;
+        }
+        buf.put_u8(value as u8);
+        buf.put(bytes);
+        Ok(buf.freeze())
+    }
+}
+
+impl LengthPrefixed for VarintFramer {}
\ No newline at end of file
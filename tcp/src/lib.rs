@@ -1,9 +1,12 @@
 #![feature(sync_unsafe_cell)]
 
 mod error;
+mod buffer;
 mod framer;
 mod message;
 mod dispatcher;
+mod codec;
+mod packet;
 mod listener;
 mod socket;
 mod component;
@@ -18,12 +21,53 @@ pub(crate) use socket::builder::Builder as SocketBuilder;
 pub(crate) use socket::creator::Creator as SocketCreator;
 pub(crate) use socket::connection::ConnectionReader;
 pub(crate) use socket::connection::ConnectionWriter;
+pub(crate) use socket::options::Options as SocketOptions;
+pub(crate) use socket::fragment::Fragmenter;
+pub(crate) use socket::fragment::Interleaver;
+pub(crate) use socket::fragment::Reassembler;
+pub(crate) use socket::activity::Activity;
 pub(crate) use component::builder::Builder as ComponentBuilder;
 pub(crate) use component::creator::ComponentCreator;
 
 pub use error::error::Error;
+pub use buffer::buffer_pool::BufferPool;
+pub use buffer::buffer_pool::PoolStats;
+pub use framer::framer::Frame;
 pub use framer::framer::Framer;
+pub use framer::framer::FramerFactory;
+pub use framer::framer::LengthPrefixed;
+pub use framer::checksum_framer::ChecksumFramer;
+#[cfg(feature = "deflate")]
+pub use framer::deflate::Deflate;
+#[cfg(feature = "deflate")]
+pub use framer::deflate::DeflateLayer;
+pub use framer::delimiter_framer::DelimiterFramer;
+pub use framer::delimiter_framer::LinesFramer;
+pub use framer::handshake::Handshake;
+pub use framer::length_field_framer::Endian;
+pub use framer::length_field_framer::LengthFieldFramer;
+pub use framer::pipeline::Layer;
+pub use framer::pipeline::LayerFactory;
+pub use framer::pipeline::Pipeline;
+pub use framer::varint_framer::VarintFramer;
 pub use dispatcher::dispatcher::Dispatcher;
+pub use dispatcher::typed_dispatcher::TypedDispatcher;
+pub use dispatcher::typed_dispatcher::CodecDispatcher;
+pub use dispatcher::router::Router;
+pub use dispatcher::router::RouteStats;
+pub use codec::codec::Codec;
+pub use packet::packet::Layout;
+pub use packet::packet::Packet;
+pub use packet::packet::PacketCodec;
+pub use packet::packet::PacketField;
+pub use packet::packet_reader::PacketReader;
+pub use packet::packet_writer::PacketWriter;
+#[cfg(feature = "derive")]
+pub use packet_derive::Packet;
+#[cfg(feature = "json")]
+pub use codec::json_codec::JsonCodec;
+#[cfg(feature = "bincode")]
+pub use codec::bincode_codec::BincodeCodec;
 pub use listener::listener::Listener;
 pub use socket::socket::Socket;
 pub use component::builder::Builder;
@@ -31,4 +75,10 @@ pub use component::component::Component;
 pub use component::component::ServerComponent;
 pub use listener::default_listener::DefaultListener;
 pub use socket::default_socket::DefaultSocket;
-pub use component::default_component::DefaultComponent;
\ No newline at end of file
+pub use component::default_component::DefaultComponent;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use bytes::Bytes;
+    pub use bytes::BytesMut;
+}
\ No newline at end of file
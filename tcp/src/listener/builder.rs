@@ -1,5 +1,6 @@
-use crate::Framer;
+use crate::FramerFactory;
 use crate::Message;
+use crate::SocketOptions;
 use std::sync::Arc;
 use std::net::SocketAddr;
 use tokio::sync::mpsc::Sender as MpscSender;
@@ -7,15 +8,17 @@ use tokio::sync::broadcast::Receiver as BroadcastReceiver;
 
 pub struct Builder {
     pub(crate) socket_events: usize,
+    pub(crate) options: SocketOptions,
     pub(crate) addr: SocketAddr,
-    pub(crate) framer: Arc<dyn Framer>,
+    pub(crate) framer: Arc<dyn FramerFactory>,
     pub(crate) message: MpscSender<Message>,
     pub(crate) shutdown: BroadcastReceiver<()>,
 }
 
 impl Builder {
-    pub(crate) fn new(socket_events: usize, addr: SocketAddr, framer: Arc<dyn Framer>, 
-        message: MpscSender<Message>, shutdown: BroadcastReceiver<()>) -> Self {
-        Self { socket_events, addr, framer, message, shutdown }
+    pub(crate) fn new(socket_events: usize, options: SocketOptions, addr: SocketAddr, 
+        framer: Arc<dyn FramerFactory>, message: MpscSender<Message>, 
+        shutdown: BroadcastReceiver<()>) -> Self {
+        Self { socket_events, options, addr, framer, message, shutdown }
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::Framer;
+use crate::FramerFactory;
 use crate::Message;
 use crate::AsyncListener;
 use crate::Listener;
@@ -6,6 +6,7 @@ use crate::ListenerBuilder;
 use crate::ListenerCreator;
 use crate::Socket;
 use crate::SocketBuilder;
+use crate::SocketOptions;
 use async_trait::async_trait;
 use tokio::select;
 use tokio::task::JoinHandle;
@@ -33,9 +34,10 @@ enum State {
 
 pub struct DefaultListener {
     socket_events: usize,
+    options: SocketOptions,
     addr: SocketAddr,
     state: AtomicU8,
-    framer: Arc<dyn Framer>,
+    framer: Arc<dyn FramerFactory>,
     message: MpscSender<Message>,
     shutdown: BroadcastReceiver<()>,
     close: BroadcastSender<()>,
@@ -88,6 +90,7 @@ impl ListenerCreator for DefaultListener {
         let (close, _) = broadcast::channel(1);
         Arc::new(Self {
             socket_events: builder.socket_events,
+            options: builder.options,
             addr: builder.addr,
             state: AtomicU8::new(State::Binding as u8),
             framer: builder.framer,
@@ -137,6 +140,7 @@ impl DefaultListener {
 
                 let builder = SocketBuilder::new(
                     self.socket_events,
+                    self.options.clone(),
                     self.framer.clone(),
                     self.message.clone(),
                     self.shutdown.resubscribe(),
@@ -2,6 +2,7 @@ use crate::Listener;
 use crate::Socket;
 use crate::Error;
 use std::sync::Arc;
+use tokio::sync::oneshot::Sender as OneshotSender;
 use bytes::Bytes;
 
 pub(crate) enum Message {
@@ -9,6 +10,8 @@ pub(crate) enum Message {
     ConnectFatal(Arc<dyn Socket>, Error),
     ConnectDone(Option<Arc<dyn Listener>>, Arc<dyn Socket>),
     ReceiveDone(Arc<dyn Socket>, Bytes),
+    ReceiveHandshake(Arc<dyn Socket>, Bytes, OneshotSender<()>),
     ConnectAbort(Arc<dyn Socket>, Error),
     ConnectTerminate(Arc<dyn Socket>),
+    SendDropped(Arc<dyn Socket>, usize),
 }
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod packet;
+pub mod packet_reader;
+pub mod packet_writer;
\ No newline at end of file
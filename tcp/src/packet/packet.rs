@@ -0,0 +1,219 @@
+use crate::Codec;
+use crate::Endian;
+use crate::Error;
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+use std::mem;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub endian: Endian,
+    pub prefix: usize,
+}
+
+pub trait Packet: Sized {
+    fn encode(&self, buf: &mut BytesMut) -> Result<(), Error>;
+    fn decode(buf: &mut Bytes) -> Result<Self, Error>;
+
+    fn to_bytes(&self) -> Result<Bytes, Error> {
+        let mut buf = BytesMut::new();
+        self.encode(&mut buf)?;
+        Ok(buf.freeze())
+    }
+
+    fn from_bytes(mut bytes: Bytes) -> Result<Self, Error> {
+        let packet = Self::decode(&mut bytes)?;
+        if bytes.has_remaining() {
+            return Err(Error::Codec(format!("{} trailing bytes after the packet", bytes.remaining())));
+        }
+        Ok(packet)
+    }
+}
+
+pub trait PacketField: Sized {
+    fn encode_field(&self, buf: &mut BytesMut, layout: Layout) -> Result<(), Error>;
+    fn decode_field(buf: &mut Bytes, layout: Layout) -> Result<Self, Error>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PacketCodec<T> {
+    packet: PhantomData<fn() -> T>,
+}
+
+impl Layout {
+    pub const fn new(endian: Endian, prefix: usize) -> Self {
+        Self { endian, prefix }
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self::new(Endian::Little, mem::size_of::<u16>())
+    }
+}
+
+impl<T> Default for PacketCodec<T> {
+    fn default() -> Self {
+        Self { packet: PhantomData }
+    }
+}
+
+impl<T> Codec<T> for PacketCodec<T>
+where
+    T: Packet,
+{
+    fn encode(&self, message: &T) -> Result<Bytes, Error> {
+        message.to_bytes()
+    }
+
+    fn decode(&self, bytes: Bytes) -> Result<T, Error> {
+        T::from_bytes(bytes)
+    }
+}
+
+impl<T> PacketField for T
+where
+    T: Packet,
+{
+    fn encode_field(&self, buf: &mut BytesMut, _layout: Layout) -> Result<(), Error> {
+        self.encode(buf)
+    }
+
+    fn decode_field(buf: &mut Bytes, _layout: Layout) -> Result<Self, Error> {
+        T::decode(buf)
+    }
+}
+
+pub(crate) fn ensure(buf: &Bytes, size: usize) -> Result<(), Error> {
+    if buf.remaining() < size {
+        return Err(Error::Codec(format!(
+            "truncated packet, {} bytes needed but {} remaining", size, buf.remaining()
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn put_prefix(buf: &mut BytesMut, len: usize, layout: Layout) -> Result<(), Error> {
+    let max = if layout.prefix >= mem::size_of::<u64>() { u64::MAX } else { (1u64 << (layout.prefix * 8)) - 1 };
+    if len as u64 > max {
+        return Err(Error::Codec(format!("length {} does not fit a {} byte prefix", len, layout.prefix)));
+    }
+
+    match layout.endian {
+        Endian::Big => buf.put_uint(len as u64, layout.prefix),
+        Endian::Little => buf.put_uint_le(len as u64, layout.prefix),
+    }
+    Ok(())
+}
+
+pub(crate) fn get_prefix(buf: &mut Bytes, layout: Layout) -> Result<usize, Error> {
+    ensure(buf, layout.prefix)?;
+    let len = match layout.endian {
+        Endian::Big => buf.get_uint(layout.prefix),
+        Endian::Little => buf.get_uint_le(layout.prefix),
+    };
+    usize::try_from(len).map_err(|_| Error::Codec(format!("length {} is too large", len)))
+}
+
+macro_rules! packet_number {
+    ($($ty:ty => $put_be:ident, $put_le:ident, $get_be:ident, $get_le:ident;)*) => {
+        $(
+            impl PacketField for $ty {
+                fn encode_field(&self, buf: &mut BytesMut, layout: Layout) -> Result<(), Error> {
+                    match layout.endian {
+                        Endian::Big => buf.$put_be(*self),
+                        Endian::Little => buf.$put_le(*self),
+                    }
+                    Ok(())
+                }
+
+                fn decode_field(buf: &mut Bytes, layout: Layout) -> Result<Self, Error> {
+                    ensure(buf, mem::size_of::<$ty>())?;
+                    match layout.endian {
+                        Endian::Big => Ok(buf.$get_be()),
+                        Endian::Little => Ok(buf.$get_le()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+packet_number! {
+    u8 => put_u8, put_u8, get_u8, get_u8;
+    i8 => put_i8, put_i8, get_i8, get_i8;
+    u16 => put_u16, put_u16_le, get_u16, get_u16_le;
+    i16 => put_i16, put_i16_le, get_i16, get_i16_le;
+    u32 => put_u32, put_u32_le, get_u32, get_u32_le;
+    i32 => put_i32, put_i32_le, get_i32, get_i32_le;
+    u64 => put_u64, put_u64_le, get_u64, get_u64_le;
+    i64 => put_i64, put_i64_le, get_i64, get_i64_le;
+    f32 => put_f32, put_f32_le, get_f32, get_f32_le;
+    f64 => put_f64, put_f64_le, get_f64, get_f64_le;
+}
+
+impl PacketField for bool {
+    fn encode_field(&self, buf: &mut BytesMut, _layout: Layout) -> Result<(), Error> {
+        buf.put_u8(*self as u8);
+        Ok(())
+    }
+
+    fn decode_field(buf: &mut Bytes, _layout: Layout) -> Result<Self, Error> {
+        ensure(buf, mem::size_of::<u8>())?;
+        match buf.get_u8() {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(Error::Codec(format!("invalid bool value {}", value))),
+        }
+    }
+}
+
+impl PacketField for String {
+    fn encode_field(&self, buf: &mut BytesMut, layout: Layout) -> Result<(), Error> {
+        put_prefix(buf, self.len(), layout)?;
+        buf.put(self.as_bytes());
+        Ok(())
+    }
+
+    fn decode_field(buf: &mut Bytes, layout: Layout) -> Result<Self, Error> {
+        let len = get_prefix(buf, layout)?;
+        ensure(buf, len)?;
+        String::from_utf8(buf.split_to(len).to_vec()).map_err(|err| Error::Codec(err.to_string()))
+    }
+}
+
+impl PacketField for Bytes {
+    fn encode_field(&self, buf: &mut BytesMut, layout: Layout) -> Result<(), Error> {
+        put_prefix(buf, self.len(), layout)?;
+        buf.put(self.as_ref());
+        Ok(())
+    }
+
+    fn decode_field(buf: &mut Bytes, layout: Layout) -> Result<Self, Error> {
+        let len = get_prefix(buf, layout)?;
+        ensure(buf, len)?;
+        Ok(buf.split_to(len))
+    }
+}
+
+impl<T> PacketField for Vec<T>
+where
+    T: PacketField,
+{
+    fn encode_field(&self, buf: &mut BytesMut, layout: Layout) -> Result<(), Error> {
+        put_prefix(buf, self.len(), layout)?;
+        self.iter().try_for_each(|item| item.encode_field(buf, layout))
+    }
+
+    fn decode_field(buf: &mut Bytes, layout: Layout) -> Result<Self, Error> {
+        let len = get_prefix(buf, layout)?;
+        let mut items = Vec::with_capacity(len.min(buf.remaining()));
+        for _ in 0..len {
+            items.push(T::decode_field(buf, layout)?);
+        }
+        Ok(items)
+    }
+}
\ No newline at end of file
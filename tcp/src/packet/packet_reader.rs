@@ -0,0 +1,101 @@
+use crate::packet::packet::ensure;
+use crate::packet::packet::get_prefix;
+use crate::Error;
+use crate::Layout;
+use crate::PacketField;
+use bytes::Buf;
+use bytes::Bytes;
+
+const MAX_VARINT_LEN: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct PacketReader {
+    buf: Bytes,
+    layout: Layout,
+}
+
+macro_rules! get_number {
+    ($($name:ident => $ty:ty;)*) => {
+        $(
+            pub fn $name(&mut self) -> Result<$ty, Error> {
+                <$ty>::decode_field(&mut self.buf, self.layout)
+            }
+        )*
+    };
+}
+
+impl PacketReader {
+    pub fn new(buf: Bytes) -> Self {
+        Self { buf, layout: Layout::default() }
+    }
+
+    #[allow(dead_code)]
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    get_number! {
+        get_u8 => u8;
+        get_i8 => i8;
+        get_u16 => u16;
+        get_i16 => i16;
+        get_u32 => u32;
+        get_i32 => i32;
+        get_u64 => u64;
+        get_i64 => i64;
+        get_f32 => f32;
+        get_f64 => f64;
+        get_bool => bool;
+    }
+
+    pub fn get_varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0u64;
+        for i in 0..MAX_VARINT_LEN {
+            ensure(&self.buf, 1)?;
+            let byte = self.buf.get_u8();
+            if i == MAX_VARINT_LEN - 1 && byte > 1 {
+                break;
+            }
+
+            value |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::Codec(String::from("varint is too long")))
+    }
+
+    pub fn get_string(&mut self) -> Result<String, Error> {
+        String::decode_field(&mut self.buf, self.layout)
+    }
+
+    pub fn get_bytes(&mut self) -> Result<Bytes, Error> {
+        let len = get_prefix(&mut self.buf, self.layout)?;
+        self.get_slice(len)
+    }
+
+    pub fn get_slice(&mut self, len: usize) -> Result<Bytes, Error> {
+        ensure(&self.buf, len)?;
+        Ok(self.buf.split_to(len))
+    }
+
+    pub fn get<T>(&mut self) -> Result<T, Error>
+    where
+        T: PacketField,
+    {
+        T::decode_field(&mut self.buf, self.layout)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.remaining()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.buf.has_remaining()
+    }
+
+    pub fn into_inner(self) -> Bytes {
+        self.buf
+    }
+}
\ No newline at end of file
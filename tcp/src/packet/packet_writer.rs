@@ -0,0 +1,99 @@
+use crate::packet::packet::put_prefix;
+use crate::Error;
+use crate::Layout;
+use crate::PacketField;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+#[derive(Debug, Default)]
+pub struct PacketWriter {
+    buf: BytesMut,
+    layout: Layout,
+}
+
+macro_rules! put_number {
+    ($($name:ident => $ty:ty;)*) => {
+        $(
+            pub fn $name(&mut self, value: $ty) -> &mut Self {
+                let _ = value.encode_field(&mut self.buf, self.layout);
+                self
+            }
+        )*
+    };
+}
+
+impl PacketWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(dead_code)]
+    pub fn with_capacity(n: usize) -> Self {
+        Self { buf: BytesMut::with_capacity(n), layout: Layout::default() }
+    }
+
+    #[allow(dead_code)]
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    put_number! {
+        put_u8 => u8;
+        put_i8 => i8;
+        put_u16 => u16;
+        put_i16 => i16;
+        put_u32 => u32;
+        put_i32 => i32;
+        put_u64 => u64;
+        put_i64 => i64;
+        put_f32 => f32;
+        put_f64 => f64;
+        put_bool => bool;
+    }
+
+    pub fn put_varint(&mut self, mut value: u64) -> &mut Self {
+        while value >= 0x80 {
+            self.buf.put_u8((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.put_u8(value as u8);
+        self
+    }
+
+    pub fn put_string(&mut self, value: &str) -> Result<&mut Self, Error> {
+        self.put_bytes(value.as_bytes())
+    }
+
+    pub fn put_bytes(&mut self, value: &[u8]) -> Result<&mut Self, Error> {
+        put_prefix(&mut self.buf, value.len(), self.layout)?;
+        self.buf.put(value);
+        Ok(self)
+    }
+
+    pub fn put_slice(&mut self, value: &[u8]) -> &mut Self {
+        self.buf.put(value);
+        self
+    }
+
+    pub fn put<T>(&mut self, value: &T) -> Result<&mut Self, Error>
+    where
+        T: PacketField,
+    {
+        value.encode_field(&mut self.buf, self.layout)?;
+        Ok(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn finish(self) -> Bytes {
+        self.buf.freeze()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use tokio::time::Instant;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+pub(crate) struct Activity {
+    epoch: Instant,
+    last: AtomicU64,
+}
+
+impl Activity {
+    pub(crate) fn new() -> Self {
+        Self { epoch: Instant::now(), last: AtomicU64::new(0) }
+    }
+
+    pub(crate) fn touch(&self) {
+        self.last.store(self.epoch.elapsed().as_millis() as u64, Ordering::SeqCst);
+    }
+
+    pub(crate) fn last(&self) -> Instant {
+        self.epoch + Duration::from_millis(self.last.load(Ordering::SeqCst))
+    }
+}
\ No newline at end of file
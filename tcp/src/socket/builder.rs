@@ -1,20 +1,22 @@
-use crate::Framer;
+use crate::FramerFactory;
 use crate::Message;
+use crate::SocketOptions;
 use tokio::sync::mpsc::Sender as MpscSender;
 use tokio::sync::broadcast::Receiver as BroadcastReceiver;
 use std::sync::Arc;
 
 pub struct Builder {
     pub(crate) events: usize,
-    pub(crate) framer: Arc<dyn Framer>,
+    pub(crate) options: SocketOptions,
+    pub(crate) framer: Arc<dyn FramerFactory>,
     pub(crate) message: MpscSender<Message>,
     pub(crate) shutdown: BroadcastReceiver<()>,
 }
 
 impl Builder {
-    pub(crate) fn new(events: usize, framer: Arc<dyn Framer>, 
+    pub(crate) fn new(events: usize, options: SocketOptions, framer: Arc<dyn FramerFactory>, 
         message: MpscSender<Message>, shutdown: BroadcastReceiver<()>) -> Self {
-        Self { events, framer, message, shutdown }
+        Self { events, options, framer, message, shutdown }
     }
 
     #[allow(dead_code)]
@@ -1,32 +1,75 @@
 use crate::Error;
+use crate::Frame;
 use crate::Framer;
+use crate::SocketOptions;
+use crate::Activity;
+use crate::BufferPool;
+use tokio::select;
 use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tokio::net::tcp::OwnedReadHalf;
 use tokio::net::tcp::OwnedWriteHalf;
+use tokio::sync::Notify;
+use tokio::time;
+use tokio::time::Instant;
+use bytes::Buf;
 use bytes::Bytes;
 use bytes::BytesMut;
+use std::mem;
+use std::future;
 use std::io::Cursor;
-use std::sync::Arc;
+use std::io::IoSlice;
+use std::io::ErrorKind;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::collections::VecDeque;
+
+const MAX_SLICES: usize = 64;
+const SHRINK_FACTOR: usize = 4;
+const SHRINK_IDLE: Duration = Duration::from_secs(1);
 
 pub(crate) struct ConnectionReader<'a> {
     buf: BytesMut,
+    options: &'a SocketOptions,
+    hint: usize,
+    estimate: usize,
+    started: Instant,
+    handshake: bool,
     stream: &'a mut OwnedReadHalf,
-    framer: &'a Arc<dyn Framer>,
+    framer: &'a Mutex<Box<dyn Framer>>,
+    swap: &'a Notify,
+    activity: &'a Activity,
 }
 
-pub(crate) struct ConnectionWriter<'a> {
-    stream: &'a mut OwnedWriteHalf,
+pub(crate) struct ConnectionWriter<'a, W = OwnedWriteHalf> {
+    stream: &'a mut W,
+    pending: VecDeque<Bytes>,
+    queued: usize,
+    timeout: Option<Duration>,
+    pool: Option<&'a BufferPool>,
 }
 
 impl<'a> ConnectionReader<'a> {
-    pub(crate) fn new(size: usize, stream: &'a mut OwnedReadHalf, 
-        framer: &'a Arc<dyn Framer>) -> Self {
-        Self { 
-            buf: BytesMut::with_capacity(size),
+    pub(crate) fn new(options: &'a SocketOptions, stream: &'a mut OwnedReadHalf, 
+        framer: &'a Mutex<Box<dyn Framer>>, swap: &'a Notify, activity: &'a Activity) -> Self {
+        let mut reader = Self { 
+            buf: BytesMut::new(),
+            options,
+            hint: 0,
+            estimate: options.read_buffer,
+            started: Instant::now(),
+            handshake: false,
             stream,
             framer,
+            swap,
+            activity,
+        };
+
+        if !options.adaptive {
+            reader.buf = reader.allocate(options.read_buffer);
         }
+        reader
     }
 
     pub(crate) async fn read_frame(&mut self) -> Result<Option<Bytes>, Error> {
@@ -35,36 +78,305 @@ impl<'a> ConnectionReader<'a> {
                 return Ok(Some(bytes));
             }
 
-            if 0 == (*self.stream).read_buf(&mut self.buf).await? {
-                if self.buf.is_empty() {
-                    return Ok(None)
-                } else {
-                    return Err(Error::Module(String::from("connection reset by peer")))
+            let deadline = self.options.read_timeout.map(|timeout| self.started + timeout);
+            if self.options.adaptive && self.buf.is_empty() {
+                let floor = self.target().max(self.options.read_buffer);
+                if self.buf.capacity() > floor.saturating_mul(SHRINK_FACTOR) {
+                    self.shrink();
+                }
+
+                let idle = (0 < self.buf.capacity()).then(|| Instant::now() + SHRINK_IDLE);
+                select! {
+                    result = self.stream.readable() => result?,
+                    _ = self.swap.notified() => continue,
+                    _ = expire(idle) => {
+                        self.shrink();
+                        continue
+                    },
+                    _ = expire(deadline) => return Err(self.expired()),
                 }
             }
+
+            self.reserve();
+            select! {
+                result = (*self.stream).read_buf(&mut self.buf) => {
+                    if 0 == result? {
+                        if self.buf.is_empty() {
+                            return Ok(None)
+                        } else {
+                            return Err(Error::Module(String::from("connection reset by peer")))
+                        }
+                    }
+                    self.activity.touch();
+                },
+                _ = self.swap.notified() => (),
+                _ = expire(deadline) => return Err(self.expired()),
+            }
+        }
+    }
+
+    pub(crate) fn handshake(&self) -> bool {
+        self.handshake
+    }
+
+    fn expired(&self) -> Error {
+        Error::ReadTimeout(format!(
+            "no complete frame within {:?}", self.options.read_timeout.unwrap_or_default()
+        ))
+    }
+
+    fn target(&self) -> usize {
+        self.hint.max(self.estimate).min(self.options.read_buffer_max)
+    }
+
+    fn allocate(&self, size: usize) -> BytesMut {
+        match &self.options.pool {
+            Some(pool) if size <= pool.size() => pool.acquire(),
+            _ => BytesMut::with_capacity(size),
+        }
+    }
+
+    fn shrink(&mut self) {
+        let buf = mem::take(&mut self.buf);
+        self.recycle(buf);
+    }
+
+    fn recycle(&self, buf: BytesMut) {
+        if let Some(pool) = &self.options.pool {
+            pool.release(buf);
+        }
+    }
+
+    fn reserve(&mut self) {
+        if 0 == self.buf.capacity() {
+            let size = match self.options.adaptive {
+                true => self.target(),
+                false => self.options.read_buffer,
+            };
+            self.buf = self.allocate(size);
+        }
+
+        let spare = self.buf.capacity() - self.buf.len();
+        if self.options.adaptive {
+            let target = self.target().saturating_sub(self.buf.len());
+            if spare < target {
+                self.buf.reserve(target);
+            }
+        } else if 0 == spare {
+            self.buf.reserve(self.options.read_buffer);
         }
     }
 
     pub(crate) fn parse_frame(&mut self) -> Result<Option<Bytes>, Error> {
+        let mut framer = self.framer.lock().unwrap();
         let mut cursor = Cursor::new(&self.buf);
-        match self.framer.clone().check(&mut cursor) {
-            Ok(0) => Ok(None),
-            Ok(len) => {
-                let frame = self.buf.split_to(len as usize).freeze();
-                Ok(Some(frame))
+        let limit = self.options.frame_limit;
+        match framer.check(&mut cursor, limit)? {
+            Frame::Success(header, payload, trailer) => {
+                let size = header + payload + trailer;
+                self.handshake = framer.handshake();
+                self.hint = 0;
+                self.started = Instant::now();
+                self.estimate = (self.estimate * 7 + size) / 8;
+
+                let mut frame = self.buf.split_to(size).freeze();
+                frame.truncate(header + payload);
+                if !framer.keep_header() {
+                    frame.advance(header);
+                }
+                framer.decode(frame).map(Some)
+            },
+            _ if self.buf.len() >= limit => Err(
+                Error::Oversize(format!("no frame found within the limit of {} bytes", limit))
+            ),
+            Frame::Partial(size) => {
+                self.hint = size;
+                Ok(None)
             },
             _ => Ok(None),
         }
     }
 }
 
-impl<'a> ConnectionWriter<'a> {
-    pub(crate) fn new(stream: &'a mut OwnedWriteHalf) -> Self {
-        Self { stream }
+impl<'a> Drop for ConnectionReader<'a> {
+    fn drop(&mut self) {
+        self.shrink();
     }
+}
 
-    pub(crate) async fn write_frame(&mut self, bytes: Bytes) -> Result<(), Error> {
-        self.stream.write_all(bytes.as_ref()).await?;
+impl<'a, W> ConnectionWriter<'a, W>
+where
+    W: AsyncWrite + Unpin,
+{
+    pub(crate) fn new(stream: &'a mut W, timeout: Option<Duration>, 
+        pool: Option<&'a BufferPool>) -> Self {
+        Self { stream, pending: VecDeque::new(), queued: 0, timeout, pool }
+    }
+
+    pub(crate) fn queued(&self) -> usize {
+        self.queued
+    }
+
+    pub(crate) fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub(crate) fn queue_frame(&mut self, frame: Bytes) {
+        if !frame.is_empty() {
+            self.queued += frame.len();
+            self.pending.push_back(frame);
+        }
+    }
+
+    pub(crate) async fn shutdown(&mut self) -> Result<(), Error> {
+        self.stream.shutdown().await?;
         Ok(())
     }
+
+    pub(crate) async fn flush(&mut self) -> Result<(), Error> {
+        while !self.pending.is_empty() {
+            let slices = self.pending
+                .iter()
+                .take(MAX_SLICES)
+                .map(|frame| IoSlice::new(frame))
+                .collect::<Vec<_>>();
+
+            let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+            let mut written = select! {
+                result = self.stream.write_vectored(&slices) => result?,
+                _ = expire(deadline) => {
+                    return Err(Error::WriteTimeout(format!(
+                        "peer has not drained the send queue within {:?}", self.timeout.unwrap_or_default()
+                    )))
+                },
+            };
+            if 0 == written {
+                return Err(std::io::Error::from(ErrorKind::WriteZero).into());
+            }
+
+            self.queued -= written;
+            while 0 < written {
+                let frame = self.pending.front_mut().unwrap();
+                if frame.len() > written {
+                    frame.advance(written);
+                    break;
+                }
+                written -= frame.len();
+                let frame = self.pending.pop_front().unwrap();
+                if let Some(pool) = self.pool {
+                    pool.reclaim(frame);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn expire(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => time::sleep_until(deadline).await,
+        None => future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+
+    struct ShortWriter {
+        limit: usize,
+        budget: usize,
+        written: Vec<u8>,
+        slices: Vec<usize>,
+    }
+
+    impl ShortWriter {
+        fn new(limit: usize, budget: usize) -> Self {
+            Self { limit, budget, written: Vec::new(), slices: Vec::new() }
+        }
+    }
+
+    impl AsyncWrite for ShortWriter {
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, 
+            buf: &[u8]) -> Poll<io::Result<usize>> {
+            self.poll_write_vectored(cx, &[IoSlice::new(buf)])
+        }
+
+        fn poll_write_vectored(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, 
+            bufs: &[IoSlice<'_>]) -> Poll<io::Result<usize>> {
+            if 0 == self.budget {
+                return Poll::Pending;
+            }
+
+            self.slices.push(bufs.len());
+            let mut left = self.limit.min(self.budget);
+            for buf in bufs {
+                let n = left.min(buf.len());
+                self.written.extend_from_slice(&buf[..n]);
+                left -= n;
+            }
+
+            let n = self.limit.min(self.budget) - left;
+            self.budget -= n;
+            Poll::Ready(Ok(n))
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            true
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn flush_survives_short_writes() {
+        let mut stream = ShortWriter::new(4, usize::MAX);
+        let mut writer = ConnectionWriter::new(&mut stream, None, None);
+        for frame in [&b"abc"[..], b"defgh", b"", b"ij"] {
+            writer.queue_frame(Bytes::from_static(frame));
+        }
+        assert_eq!((writer.queued(), writer.pending()), (10, 3));
+
+        writer.flush().await.unwrap();
+        assert_eq!((writer.queued(), writer.pending()), (0, 0));
+        assert_eq!(stream.written, b"abcdefghij");
+        assert_eq!(stream.slices, vec![3, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn flush_caps_slices_per_write() {
+        let mut stream = ShortWriter::new(usize::MAX, usize::MAX);
+        let mut writer = ConnectionWriter::new(&mut stream, None, None);
+        for _ in 0..MAX_SLICES + 6 {
+            writer.queue_frame(Bytes::from_static(b"x"));
+        }
+
+        writer.flush().await.unwrap();
+        assert_eq!(stream.slices, vec![MAX_SLICES, 6]);
+        assert_eq!(stream.written.len(), MAX_SLICES + 6);
+    }
+
+    #[tokio::test]
+    async fn flush_keeps_the_unwritten_tail() {
+        let mut stream = ShortWriter::new(4, 4);
+        let mut writer = ConnectionWriter::new(&mut stream, Some(Duration::from_millis(10)), None);
+        writer.queue_frame(Bytes::from_static(b"abc"));
+        writer.queue_frame(Bytes::from_static(b"defgh"));
+
+        let err = writer.flush().await;
+        assert!(matches!(err, Err(Error::WriteTimeout(_))));
+        assert_eq!((writer.queued(), writer.pending()), (4, 1));
+        assert_eq!(&writer.pending[0][..], b"efgh");
+        assert_eq!(stream.written, b"abcd");
+    }
 }
\ No newline at end of file
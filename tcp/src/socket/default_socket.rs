@@ -1,5 +1,7 @@
 use crate::Error;
+use crate::BufferPool;
 use crate::Framer;
+use crate::DefaultFramer;
 use crate::Message;
 use crate::AsyncSocket;
 use crate::Socket;
@@ -7,10 +9,23 @@ use crate::SocketBuilder;
 use crate::SocketCreator;
 use crate::ConnectionReader;
 use crate::ConnectionWriter;
+use crate::SocketOptions;
+use crate::Fragmenter;
+use crate::Interleaver;
+use crate::Reassembler;
+use crate::Activity;
 use async_trait::async_trait;
 use tokio::select;
+use tokio::task;
+use tokio::time;
+use tokio::runtime::Handle;
+use tokio::runtime::Builder as RuntimeBuilder;
+use tokio::runtime::RuntimeFlavor;
+use tokio::sync::Notify;
+use tokio::sync::oneshot;
 use tokio::sync::OwnedSemaphorePermit;
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::Permit;
 use tokio::sync::mpsc::Sender as MpscSender;
 use tokio::sync::mpsc::Receiver as MpscReceiver;
 use tokio::sync::mpsc::error::TrySendError;
@@ -23,16 +38,21 @@ use tokio::net::tcp::OwnedWriteHalf;
 use tokio::task::JoinHandle;
 use std::cell::SyncUnsafeCell;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicU8;
 use std::sync::atomic::Ordering;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::net::SocketAddr;
+use std::future;
+use std::mem;
+use std::collections::VecDeque;
 use bytes::Bytes;
 
 enum Event {
     Send(Bytes),
-    Terminate,
+    Fragments(VecDeque<Bytes>),
+    Close(Vec<Bytes>),
 }
 
 #[derive(Debug)]
@@ -45,7 +65,10 @@ enum State {
 
 pub struct DefaultSocket {
     state: AtomicU8,
-    framer: Arc<dyn Framer>,
+    options: SocketOptions,
+    framer: Mutex<Box<dyn Framer>>,
+    fragmenter: Fragmenter,
+    swap: Notify,
     local: SyncUnsafeCell<Option<SocketAddr>>,
     peer: SyncUnsafeCell<Option<SocketAddr>>,
     message: MpscSender<Message>,
@@ -54,6 +77,25 @@ pub struct DefaultSocket {
     shutdown: BroadcastReceiver<()>,
     terminate: BroadcastSender<()>,
     tag: SyncUnsafeCell<Option<usize>>,
+    activity: Activity,
+}
+
+impl Event {
+    fn frames(&self) -> usize {
+        match self {
+            Event::Send(_) => 1,
+            Event::Fragments(frames) => frames.len(),
+            Event::Close(frames) => frames.len(),
+        }
+    }
+
+    fn into_frames(self) -> VecDeque<Bytes> {
+        match self {
+            Event::Send(frame) => VecDeque::from([frame]),
+            Event::Fragments(frames) => frames,
+            Event::Close(frames) => VecDeque::from(frames),
+        }
+    }
 }
 
 impl Debug for DefaultSocket {
@@ -85,32 +127,60 @@ impl Display for DefaultSocket {
     }
 }
 
+#[async_trait]
 impl Socket for DefaultSocket {
     fn send(self: Arc<Self>, bytes: Bytes) -> Result<(), Error> {
-        if State::Done as u8 == self.state.load(Ordering::SeqCst) {            
-            match self.etx.try_send(Event::Send(bytes)) {
-                Ok(_) => return Ok(()),
-                Err(err @ TrySendError::Closed(_)) => return Err(err.into()),
-                Err(TrySendError::Full(event)) => {
-                    self.etx.blocking_send(event)?;
-                    return Ok(());
-                },
-            }
-        }
+        self.established()?;
+        self.post(|framer| self.encode(framer, bytes))
+    }
 
-        Err(Error::Module(String::from("socket has not been established")))
+    fn try_send(self: Arc<Self>, bytes: Bytes) -> Result<(), Error> {
+        self.established()?;
+        let permit = self.etx.try_reserve()?;
+        self.commit(permit, |framer| self.encode(framer, bytes))
+    }
+
+    async fn send_async(self: Arc<Self>, bytes: Bytes) -> Result<(), Error> {
+        self.established()?;
+        let permit = self.etx.reserve().await?;
+        self.commit(permit, |framer| self.encode(framer, bytes))
     }
     
     fn disconnect(self: Arc<Self>) {
-        let _ = self.state.compare_exchange(
-            State::Done as u8,
-            State::Shutdown as u8, 
-            Ordering::SeqCst, 
-            Ordering::SeqCst
-        ).and_then(|_| {
-            let _ = self.etx.send(Event::Terminate);
-            Ok(())
+        let result = self.state.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |state| {
+            match State::Done as u8 == state || State::Shutdown as u8 == state {
+                true => Some(State::Disconnected as u8),
+                false => None,
+            }
         });
+
+        if result.is_ok() {
+            let _ = self.terminate.send(());
+        }
+    }
+
+    fn close(self: Arc<Self>) {
+        let _ = self.finish(Vec::new());
+    }
+
+    fn send_and_close(self: Arc<Self>, bytes: Bytes) -> Result<(), Error> {
+        self.finish(vec![bytes])
+    }
+
+    fn send_all_and_close(self: Arc<Self>, frames: Vec<Bytes>) -> Result<(), Error> {
+        self.finish(frames)
+    }
+
+    fn set_framer(self: Arc<Self>, framer: Box<dyn Framer>) {
+        let mut current = self.framer.lock().unwrap();
+        let previous = mem::replace(&mut *current, Box::new(DefaultFramer));
+        *current = previous.replace(framer);
+        drop(current);
+        self.swap.notify_one();
+    }
+
+    fn buffer_pool(self: Arc<Self>) -> Option<Arc<BufferPool>> {
+        self.options.pool.clone()
     }
 
     fn local_addr(self: Arc<Self>) -> Option<SocketAddr> {
@@ -169,7 +239,10 @@ impl SocketCreator for DefaultSocket {
         Arc::new(
             Self {
                 state: AtomicU8::new(State::Connecting as u8),
-                framer: builder.framer,
+                fragmenter: Fragmenter::new(builder.options.fragment),
+                options: builder.options,
+                framer: Mutex::new(builder.framer.create()),
+                swap: Notify::new(),
                 local: SyncUnsafeCell::new(None),
                 peer: SyncUnsafeCell::new(None),
                 message: builder.message,
@@ -178,17 +251,94 @@ impl SocketCreator for DefaultSocket {
                 shutdown: builder.shutdown,
                 terminate,
                 tag: SyncUnsafeCell::new(None),
+                activity: Activity::new(),
             }
         )
     }
 }
 
 impl DefaultSocket {
+    fn established(&self) -> Result<(), Error> {
+        match State::Done as u8 == self.state.load(Ordering::SeqCst) {
+            true => Ok(()),
+            false => Err(Error::Module(String::from("socket has not been established"))),
+        }
+    }
+
+    fn finish(&self, frames: Vec<Bytes>) -> Result<(), Error> {
+        self.state.compare_exchange(
+            State::Done as u8,
+            State::Shutdown as u8, 
+            Ordering::SeqCst, 
+            Ordering::SeqCst
+        ).map_err(|_| Error::Module(String::from("socket has not been established")))?;
+
+        self.post(|framer| self.encode_all(framer, frames)).inspect_err(|_| {
+            let _ = self.state.compare_exchange(
+                State::Shutdown as u8,
+                State::Done as u8, 
+                Ordering::SeqCst, 
+                Ordering::SeqCst
+            );
+        })
+    }
+
+    fn encode(&self, framer: &mut dyn Framer, bytes: Bytes) -> Result<Event, Error> {
+        let payload = self.options.pool.as_ref().map(|pool| (pool, bytes.clone()));
+        let event = match self.fragmenter.fits(&bytes) {
+            true => Event::Send(framer.encode(self.fragmenter.whole(bytes))?),
+            false => Event::Fragments(
+                self.fragmenter
+                    .split(bytes)
+                    .into_iter()
+                    .map(|chunk| framer.encode(chunk))
+                    .collect::<Result<_, _>>()?
+            ),
+        };
+
+        if let Some((pool, payload)) = payload {
+            pool.reclaim(payload);
+        }
+        Ok(event)
+    }
+
+    fn encode_all(&self, framer: &mut dyn Framer, frames: Vec<Bytes>) -> Result<Event, Error> {
+        let mut encoded = Vec::with_capacity(frames.len());
+        for bytes in frames {
+            encoded.extend(self.encode(framer, bytes)?.into_frames());
+        }
+        Ok(Event::Close(encoded))
+    }
+
+    fn commit<F>(&self, permit: Permit<'_, Event>, encode: F) -> Result<(), Error>
+        where F: FnOnce(&mut dyn Framer) -> Result<Event, Error> {
+        let mut framer = self.framer.lock().unwrap();
+        permit.send(encode(&mut **framer)?);
+        Ok(())
+    }
+
+    fn post<F>(&self, encode: F) -> Result<(), Error>
+        where F: FnOnce(&mut dyn Framer) -> Result<Event, Error> {
+        let permit = match self.etx.try_reserve() {
+            Ok(permit) => permit,
+            Err(err @ TrySendError::Closed(_)) => return Err(err.into()),
+            Err(err @ TrySendError::Full(_)) => match Handle::try_current() {
+                Ok(handle) if RuntimeFlavor::MultiThread == handle.runtime_flavor() => {
+                    task::block_in_place(|| handle.block_on(self.etx.reserve()))?
+                },
+                Ok(_) => return Err(err.into()),
+                Err(_) => RuntimeBuilder::new_current_thread().build()?.block_on(self.etx.reserve())?,
+            },
+        };
+        self.commit(permit, encode)
+    }
+
     fn start(self: &Arc<Self>, permit: OwnedSemaphorePermit, reader: OwnedReadHalf,
         writer: OwnedWriteHalf, receiver: MpscReceiver<Event>) -> JoinHandle<()> {
         let cloned = self.clone();
         let mut shutdown = self.shutdown.resubscribe();
         let mut terminate = self.terminate.subscribe();
+        self.touch();
 
         tokio::spawn(async move {
             select! {
@@ -196,8 +346,25 @@ impl DefaultSocket {
                     drop(permit);
                     return;
                 },
-                _ = cloned.write(writer, receiver) => {
-                    panic!("system error");
+                result = cloned.write(writer, receiver) => {
+                    cloned.state.store(State::Disconnected as u8, Ordering::SeqCst);
+                    match result {
+                        Ok(_) => {
+                            let _ = cloned.message.send(Message::ConnectTerminate(cloned.clone())).await;
+                        },
+                        Err((err, dropped)) => {
+                            if 0 < dropped {
+                                let _ = cloned.message.send(Message::SendDropped(cloned.clone(), dropped)).await;
+                            }
+                            let _ = cloned.message.send(Message::ConnectAbort(cloned.clone(), err)).await;
+                        },
+                    }
+                    drop(permit);
+                },
+                err = cloned.watch() => {
+                    cloned.state.store(State::Disconnected as u8, Ordering::SeqCst);
+                    let _ = cloned.message.send(Message::ConnectAbort(cloned.clone(), err)).await;
+                    drop(permit);
                 },
                 _ = terminate.recv() => {
                     cloned.state.store(State::Disconnected as u8, Ordering::SeqCst);
@@ -217,18 +384,30 @@ impl DefaultSocket {
 
     async fn read(self: &Arc<Self>, mut reader: OwnedReadHalf) {
         let mut connection = ConnectionReader::new(
-            4096, 
+            &self.options,
             &mut reader, 
-            &self.framer
+            &self.framer,
+            &self.swap,
+            &self.activity,
         );
 
+        let mut reassembler = Reassembler::new(self.options.fragment, self.options.reassembly);
+
         loop {
-            match connection.read_frame().await {
+            match connection.read_frame().await.and_then(|bytes| {
+                bytes.map(|bytes| reassembler.push(bytes)).transpose()
+            }) {
                 Ok(bytes) => {
                     match bytes {
-                        Some(bytes) => {
+                        Some(Some(bytes)) if connection.handshake() => {
+                            let (resume, resumed) = oneshot::channel();
+                            let _ = self.message.send(Message::ReceiveHandshake(self.clone(), bytes, resume)).await;
+                            let _ = resumed.await;
+                        },
+                        Some(Some(bytes)) => {
                             let _ = self.message.send(Message::ReceiveDone(self.clone(), bytes)).await;
                         },
+                        Some(None) => continue,
                         _ => {
                             self.state.store(State::Disconnected as u8, Ordering::SeqCst);
                             let _ = self.message.send(Message::ConnectTerminate(self.clone())).await;
@@ -245,20 +424,110 @@ impl DefaultSocket {
         }
     }
 
-    async fn write(self: &Arc<Self>, mut writer: OwnedWriteHalf, mut erx: MpscReceiver<Event>) {
-        let mut connection = ConnectionWriter::new(&mut writer);
+    async fn write(self: &Arc<Self>, mut writer: OwnedWriteHalf, 
+        mut erx: MpscReceiver<Event>) -> Result<(), (Error, usize)> {
+        let mut connection = ConnectionWriter::new(
+            &mut writer, 
+            self.options.write_timeout,
+            self.options.pool.as_deref(),
+        );
+        let mut interleaver = Interleaver::new();
 
-        while let Some(event) = erx.recv().await {
-            match event {
-                Event::Send(bytes) => {
-                    let _ = connection.write_frame(bytes).await;
+        let err = loop {
+            match self.transmit(&mut connection, &mut interleaver, &mut erx).await {
+                Ok(false) => continue,
+                Ok(true) => match self.linger(&mut connection, &mut interleaver).await {
+                    Ok(_) => return Ok(()),
+                    Err(err) => break err,
                 },
-                Event::Terminate => self.terminate().await,
+                Err(err) => break err,
             }
+        };
+
+        erx.close();
+        let mut dropped = connection.pending() + interleaver.len();
+        while let Ok(event) = erx.try_recv() {
+            dropped += event.frames();
         }
+        Err((err, dropped))
     }
 
-    async fn terminate(self: &Arc<Self>) {
-        let _ = self.terminate.send(());
+    async fn transmit(self: &Arc<Self>, connection: &mut ConnectionWriter<'_>, 
+        interleaver: &mut Interleaver, erx: &mut MpscReceiver<Event>) -> Result<bool, Error> {
+        let mut close = false;
+        if interleaver.is_empty() {
+            match erx.recv().await {
+                Some(event) => close = Self::queue(connection, interleaver, event),
+                None => return Err(Error::System(String::from("socket event channel closed"))),
+            }
+        }
+
+        while !close && connection.queued() < self.options.write_budget {
+            match erx.try_recv() {
+                Ok(event) => close = Self::queue(connection, interleaver, event),
+                Err(_) => match interleaver.next() {
+                    Some(frame) => connection.queue_frame(frame),
+                    None => break,
+                },
+            }
+        }
+
+        connection.flush().await?;
+        self.touch();
+        Ok(close)
+    }
+
+    fn touch(&self) {
+        self.activity.touch();
+    }
+
+    async fn watch(self: &Arc<Self>) -> Error {
+        let timeout = match self.options.idle_timeout {
+            Some(timeout) => timeout,
+            None => future::pending().await,
+        };
+
+        loop {
+            let activity = self.activity.last();
+            if activity.elapsed() >= timeout {
+                return Error::IdleTimeout(format!("no traffic within {:?}", timeout));
+            }
+            time::sleep_until(activity + timeout).await;
+        }
+    }
+
+    fn queue(connection: &mut ConnectionWriter<'_>, interleaver: &mut Interleaver, 
+        event: Event) -> bool {
+        match event {
+            Event::Send(frame) => {
+                connection.queue_frame(frame);
+                false
+            },
+            Event::Fragments(frames) => {
+                interleaver.push(frames);
+                false
+            },
+            Event::Close(frames) => {
+                for frame in frames {
+                    connection.queue_frame(frame);
+                }
+                true
+            },
+        }
+    }
+
+    async fn linger(self: &Arc<Self>, connection: &mut ConnectionWriter<'_>, 
+        interleaver: &mut Interleaver) -> Result<(), Error> {
+        while let Some(frame) = interleaver.next() {
+            connection.queue_frame(frame);
+            if connection.queued() >= self.options.write_budget {
+                connection.flush().await?;
+            }
+        }
+
+        connection.flush().await?;
+        connection.shutdown().await?;
+        time::sleep(self.options.linger).await;
+        Ok(())
     }
 }
\ No newline at end of file
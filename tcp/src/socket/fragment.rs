@@ -0,0 +1,226 @@
+use crate::Error;
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+use std::mem;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+const KIND_WHOLE: u8 = 0;
+const KIND_CHUNK: u8 = 1;
+const KIND_FINAL: u8 = 2;
+const CHUNK_HEADER: usize = mem::size_of::<u8>() + mem::size_of::<u32>();
+
+pub(crate) struct Fragmenter {
+    size: usize,
+    next: AtomicU32,
+}
+
+pub(crate) struct Interleaver {
+    pending: VecDeque<VecDeque<Bytes>>,
+}
+
+pub(crate) struct Reassembler {
+    enabled: bool,
+    limit: usize,
+    used: usize,
+    partial: HashMap<u32, BytesMut>,
+}
+
+impl Fragmenter {
+    pub(crate) fn new(size: usize) -> Self {
+        Self { size, next: AtomicU32::new(0) }
+    }
+
+    pub(crate) fn fits(&self, bytes: &Bytes) -> bool {
+        0 == self.size || bytes.len() <= self.size
+    }
+
+    pub(crate) fn whole(&self, bytes: Bytes) -> Bytes {
+        if 0 == self.size {
+            return bytes;
+        }
+
+        let mut buf = BytesMut::with_capacity(mem::size_of::<u8>() + bytes.len());
+        buf.put_u8(KIND_WHOLE);
+        buf.put(bytes);
+        buf.freeze()
+    }
+
+    pub(crate) fn split(&self, mut bytes: Bytes) -> Vec<Bytes> {
+        let id = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut chunks = Vec::with_capacity(bytes.len().div_ceil(self.size));
+        while !bytes.is_empty() {
+            let chunk = bytes.split_to(self.size.min(bytes.len()));
+            let kind = if bytes.is_empty() { KIND_FINAL } else { KIND_CHUNK };
+
+            let mut buf = BytesMut::with_capacity(CHUNK_HEADER + chunk.len());
+            buf.put_u8(kind);
+            buf.put_u32_le(id);
+            buf.put(chunk);
+            chunks.push(buf.freeze());
+        }
+        chunks
+    }
+}
+
+impl Interleaver {
+    pub(crate) fn new() -> Self {
+        Self { pending: VecDeque::new() }
+    }
+
+    pub(crate) fn push(&mut self, frames: VecDeque<Bytes>) {
+        if !frames.is_empty() {
+            self.pending.push_back(frames);
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Bytes> {
+        let mut frames = self.pending.pop_front()?;
+        let frame = frames.pop_front();
+        if !frames.is_empty() {
+            self.pending.push_back(frames);
+        }
+        frame
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.pending.iter().map(VecDeque::len).sum()
+    }
+}
+
+impl Reassembler {
+    pub(crate) fn new(fragment: usize, limit: usize) -> Self {
+        Self { enabled: 0 != fragment, limit, used: 0, partial: HashMap::new() }
+    }
+
+    pub(crate) fn push(&mut self, mut bytes: Bytes) -> Result<Option<Bytes>, Error> {
+        if !self.enabled {
+            return Ok(Some(bytes));
+        }
+
+        if !bytes.has_remaining() {
+            return Err(Error::Protocol(String::from("missing fragment header")));
+        }
+
+        let kind = bytes.get_u8();
+        if KIND_WHOLE == kind {
+            return Ok(Some(bytes));
+        }
+
+        if bytes.remaining() < mem::size_of::<u32>() || (KIND_CHUNK != kind && KIND_FINAL != kind) {
+            return Err(Error::Protocol(String::from("invalid fragment header")));
+        }
+
+        let id = bytes.get_u32_le();
+        self.used += bytes.len();
+        if self.used > self.limit {
+            return Err(Error::Oversize(format!(
+                "reassembly of {} bytes exceeds the limit of {}", self.used, self.limit
+            )));
+        }
+
+        self.partial.entry(id).or_default().put(bytes);
+        if KIND_CHUNK == kind {
+            return Ok(None);
+        }
+
+        let message = self.partial.remove(&id).unwrap_or_default();
+        self.used -= message.len();
+        Ok(Some(message.freeze()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(kind: u8, id: u32, data: &[u8]) -> Bytes {
+        let mut buf = BytesMut::new();
+        buf.put_u8(kind);
+        buf.put_u32_le(id);
+        buf.put_slice(data);
+        buf.freeze()
+    }
+
+    #[test]
+    fn split_and_whole() {
+        let fragmenter = Fragmenter::new(4);
+        assert!(fragmenter.fits(&Bytes::from_static(b"abcd")));
+        assert!(!fragmenter.fits(&Bytes::from_static(b"abcde")));
+        assert_eq!(&fragmenter.whole(Bytes::from_static(b"ab"))[..], &[KIND_WHOLE, b'a', b'b']);
+
+        let chunks = fragmenter.split(Bytes::from_static(b"abcdefghij"));
+        assert_eq!(chunks, vec![
+            chunk(KIND_CHUNK, 0, b"abcd"),
+            chunk(KIND_CHUNK, 0, b"efgh"),
+            chunk(KIND_FINAL, 0, b"ij"),
+        ]);
+        assert_eq!(fragmenter.split(Bytes::from_static(b"abcde"))[0][1], 1);
+
+        let disabled = Fragmenter::new(0);
+        assert!(disabled.fits(&Bytes::from(vec![0; 1 << 16])));
+        assert_eq!(&disabled.whole(Bytes::from_static(b"ab"))[..], b"ab");
+    }
+
+    #[test]
+    fn interleaves_round_robin() {
+        let mut interleaver = Interleaver::new();
+        interleaver.push(VecDeque::from([Bytes::from_static(b"a1"), Bytes::from_static(b"a2")]));
+        interleaver.push(VecDeque::new());
+        interleaver.push(VecDeque::from([Bytes::from_static(b"b1")]));
+        assert_eq!(interleaver.len(), 3);
+
+        let order = std::iter::from_fn(|| interleaver.next()).collect::<Vec<_>>();
+        assert_eq!(order, vec![&b"a1"[..], &b"b1"[..], &b"a2"[..]]);
+        assert!(interleaver.is_empty());
+    }
+
+    #[test]
+    fn reassembles_interleaved_ids() {
+        let mut reassembler = Reassembler::new(4, 1024);
+        assert_eq!(reassembler.push(chunk(KIND_CHUNK, 1, b"ab")).unwrap(), None);
+        assert_eq!(reassembler.push(chunk(KIND_CHUNK, 2, b"xy")).unwrap(), None);
+        assert_eq!(&reassembler.push(Bytes::from_static(&[KIND_WHOLE, b'w'])).unwrap().unwrap()[..], b"w");
+        assert_eq!(&reassembler.push(chunk(KIND_FINAL, 2, b"z")).unwrap().unwrap()[..], b"xyz");
+        assert_eq!(reassembler.push(chunk(KIND_CHUNK, 1, b"cd")).unwrap(), None);
+        assert_eq!(&reassembler.push(chunk(KIND_FINAL, 1, b"e")).unwrap().unwrap()[..], b"abcde");
+        assert_eq!(reassembler.used, 0);
+    }
+
+    #[test]
+    fn enforces_the_reassembly_cap() {
+        let mut reassembler = Reassembler::new(4, 8);
+        reassembler.push(chunk(KIND_CHUNK, 0, b"abcd")).unwrap();
+        reassembler.push(chunk(KIND_FINAL, 0, b"efgh")).unwrap().unwrap();
+        assert_eq!(reassembler.used, 0);
+
+        reassembler.push(chunk(KIND_CHUNK, 1, b"abcd")).unwrap();
+        reassembler.push(chunk(KIND_CHUNK, 2, b"efgh")).unwrap();
+        let err = reassembler.push(chunk(KIND_CHUNK, 1, b"i"));
+        assert!(matches!(err, Err(Error::Oversize(_))));
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let mut reassembler = Reassembler::new(4, 1024);
+        for bytes in [
+            Bytes::new(),
+            Bytes::from_static(&[7, 0, 0, 0, 0]),
+            Bytes::from_static(&[KIND_CHUNK, 0, 0]),
+            Bytes::from_static(&[KIND_FINAL]),
+        ] {
+            assert!(matches!(reassembler.push(bytes), Err(Error::Protocol(_))));
+        }
+
+        let mut disabled = Reassembler::new(0, 1024);
+        assert_eq!(&disabled.push(Bytes::from_static(&[7])).unwrap().unwrap()[..], &[7]);
+    }
+}
\ No newline at end of file
@@ -2,4 +2,7 @@ pub mod builder;
 pub mod creator;
 pub mod socket;
 pub mod connection;
-pub mod default_socket;
\ No newline at end of file
+pub mod default_socket;
+pub mod options;
+pub mod fragment;
+pub mod activity;
\ No newline at end of file
@@ -0,0 +1,19 @@
+use crate::BufferPool;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Options {
+    pub(crate) frame_limit: usize,
+    pub(crate) fragment: usize,
+    pub(crate) reassembly: usize,
+    pub(crate) write_budget: usize,
+    pub(crate) read_buffer: usize,
+    pub(crate) read_buffer_max: usize,
+    pub(crate) adaptive: bool,
+    pub(crate) pool: Option<Arc<BufferPool>>,
+    pub(crate) linger: Duration,
+    pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) read_timeout: Option<Duration>,
+    pub(crate) write_timeout: Option<Duration>,
+}
\ No newline at end of file
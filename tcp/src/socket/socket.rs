@@ -1,4 +1,8 @@
+use crate::Codec;
 use crate::Error;
+use crate::BufferPool;
+use crate::Framer;
+use crate::Packet;
 use async_trait::async_trait;
 use bytes::Bytes;
 use tokio::net::TcpStream;
@@ -14,11 +18,35 @@ pub trait AsyncSocket {
     async fn connect(self: Arc<Self>, addr: SocketAddr, permit: OwnedSemaphorePermit);
 }
 
+#[async_trait]
 pub trait Socket: AsyncSocket + Send + Sync + Debug + Display + 'static {
     fn send(self: Arc<Self>, bytes: Bytes) -> Result<(), Error>;
+    fn try_send(self: Arc<Self>, bytes: Bytes) -> Result<(), Error>;
+    async fn send_async(self: Arc<Self>, bytes: Bytes) -> Result<(), Error>;
     fn disconnect(self: Arc<Self>);
+    fn close(self: Arc<Self>);
+    fn send_and_close(self: Arc<Self>, bytes: Bytes) -> Result<(), Error>;
+    fn send_all_and_close(self: Arc<Self>, frames: Vec<Bytes>) -> Result<(), Error>;
+    fn set_framer(self: Arc<Self>, framer: Box<dyn Framer>);
+    fn buffer_pool(self: Arc<Self>) -> Option<Arc<BufferPool>>;
     fn local_addr(self: Arc<Self>) -> Option<SocketAddr>;
     fn peer_addr(self: Arc<Self>) -> Option<SocketAddr>;
     fn set_tag(self: Arc<Self>, tag: usize);
     fn tag(self: Arc<Self>) -> Option<usize>;
+}
+
+impl dyn Socket {
+    pub fn send_message<T, C>(self: Arc<Self>, codec: &C, message: &T) -> Result<(), Error>
+    where
+        C: Codec<T>,
+    {
+        self.send(codec.encode(message)?)
+    }
+
+    pub fn send_packet<P>(self: Arc<Self>, packet: &P) -> Result<(), Error>
+    where
+        P: Packet,
+    {
+        self.send(packet.to_bytes()?)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,87 @@
+#![cfg(feature = "derive")]
+
+use tcp::Packet;
+use bytes::Bytes;
+
+#[derive(Debug, PartialEq, Packet)]
+struct Login {
+    id: u32,
+    #[packet(big)]
+    port: u16,
+    name: String,
+    scores: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Packet)]
+#[packet(big, prefix = 1)]
+struct Position(i16, i16);
+
+#[derive(Debug, PartialEq, Packet)]
+struct Empty;
+
+#[derive(Debug, PartialEq, Packet)]
+struct Nested<T> {
+    position: Position,
+    value: T,
+}
+
+#[derive(Debug, PartialEq, Packet)]
+struct Shadow {
+    buf: u8,
+    tag: u8,
+}
+
+#[derive(Debug, PartialEq, Packet)]
+#[packet(tag = 2)]
+enum Command {
+    Ping,
+    #[packet(big)]
+    Move(u16),
+    #[packet(id = 7)]
+    Say { buf: String, tag: u8 },
+}
+
+#[test]
+fn struct_round_trip() {
+    let login = Login { id: 1, port: 8080, name: String::from("ab"), scores: vec![3, 4] };
+    let bytes = login.to_bytes().unwrap();
+    assert_eq!(&bytes[..], &[1, 0, 0, 0, 0x1f, 0x90, 2, 0, b'a', b'b', 2, 0, 3, 4]);
+    assert_eq!(Login::from_bytes(bytes).unwrap(), login);
+
+    let position = Position(-1, 2);
+    let bytes = position.to_bytes().unwrap();
+    assert_eq!(&bytes[..], &[0xff, 0xff, 0, 2]);
+    assert_eq!(Position::from_bytes(bytes).unwrap(), position);
+
+    assert!(Empty.to_bytes().unwrap().is_empty());
+    assert_eq!(Empty::from_bytes(Bytes::new()).unwrap(), Empty);
+
+    let nested = Nested { position: Position(5, 6), value: 9u64 };
+    assert_eq!(Nested::from_bytes(nested.to_bytes().unwrap()).unwrap(), nested);
+
+    let shadow = Shadow { buf: 1, tag: 2 };
+    assert_eq!(Shadow::from_bytes(shadow.to_bytes().unwrap()).unwrap(), shadow);
+}
+
+#[test]
+fn enum_round_trip() {
+    let bytes = Command::Move(7).to_bytes().unwrap();
+    assert_eq!(&bytes[..], &[1, 0, 0, 7]);
+    assert_eq!(Command::from_bytes(bytes).unwrap(), Command::Move(7));
+
+    let bytes = Command::Ping.to_bytes().unwrap();
+    assert_eq!(&bytes[..], &[0, 0]);
+    assert_eq!(Command::from_bytes(bytes).unwrap(), Command::Ping);
+
+    let say = Command::Say { buf: String::from("hi"), tag: 3 };
+    let bytes = say.to_bytes().unwrap();
+    assert_eq!(&bytes[..2], &[7, 0]);
+    assert_eq!(Command::from_bytes(bytes).unwrap(), say);
+}
+
+#[test]
+fn decode_errors() {
+    assert!(Command::from_bytes(Bytes::from_static(&[9, 0])).is_err());
+    assert!(Position::from_bytes(Bytes::from_static(&[0, 1, 0])).is_err());
+    assert!(Position::from_bytes(Bytes::from_static(&[0, 1, 0, 2, 0])).is_err());
+}
\ No newline at end of file
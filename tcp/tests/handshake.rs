@@ -0,0 +1,91 @@
+use tcp::Error;
+use tcp::Builder;
+use tcp::Component;
+use tcp::Dispatcher;
+use tcp::Handshake;
+use tcp::LengthFieldFramer;
+use tcp::LinesFramer;
+use tcp::Listener;
+use tcp::ServerComponent;
+use tcp::Socket;
+use tcp::DefaultComponent;
+use tcp::DefaultListener;
+use tcp::DefaultSocket;
+use bytes::Bytes;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const ADDR: &str = "127.0.0.1:46809";
+
+#[derive(Default)]
+struct Upgrade {
+    upgraded: bool,
+}
+
+impl Dispatcher for Upgrade {
+    fn listen_fatal(&mut self, _listener: Arc<dyn Listener>, _err: Error) {}
+
+    fn connect_fatal(&mut self, _socket: Arc<dyn Socket>, _err: Error) {}
+
+    fn connect_done(&mut self, _listener: Option<Arc<dyn Listener>>, _socket: Arc<dyn Socket>) {}
+
+    fn receive_done(&mut self, socket: Arc<dyn Socket>, bytes: Bytes) {
+        if self.upgraded {
+            let _ = socket.send(bytes);
+            return;
+        }
+
+        assert_eq!(&bytes[..], b"UPGRADE");
+        self.upgraded = true;
+        let _ = socket.clone().send(Bytes::from_static(b"OK"));
+        socket.set_framer(Box::new(LengthFieldFramer::default()));
+    }
+
+    fn connect_abort(&mut self, _socket: Arc<dyn Socket>, _err: Error) {}
+
+    fn connect_terminate(&mut self, _socket: Arc<dyn Socket>) {}
+}
+
+fn connect() -> TcpStream {
+    for _ in 0..100 {
+        if let Ok(stream) = TcpStream::connect(ADDR) {
+            return stream;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("server did not start listening");
+}
+
+#[test]
+fn handshake_then_upgrade() {
+    let dispatcher = Box::leak(Box::new(Upgrade::default()));
+    let mut tcp = Builder::default()
+        .listener(1)
+        .framer(Handshake::new(LinesFramer::default()))
+        .dispatcher(dispatcher)
+        .build_server::<DefaultComponent, DefaultListener, DefaultSocket>();
+    tcp.listen(ADDR.parse().unwrap()).unwrap();
+
+    let client = thread::spawn(|| {
+        let mut stream = connect();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        stream.write_all(b"UPGRADE\n\n\0\0\0two\nlines\n\x03\0\0\0bye").unwrap();
+
+        let mut reply = [0u8; 3 + 14 + 7];
+        stream.read_exact(&mut reply).unwrap();
+        reply
+    });
+
+    while !client.is_finished() {
+        if !tcp.dispatch() {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    assert_eq!(&client.join().unwrap()[..], b"OK\n\n\0\0\0two\nlines\n\x03\0\0\0bye");
+    tcp.close();
+}
\ No newline at end of file